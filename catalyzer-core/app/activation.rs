@@ -0,0 +1,96 @@
+//! Socket activation support for Catalyzer applications.
+//! 
+//! Implements the `LISTEN_FDS` / `LISTEN_PID` protocol used by systemd
+//! (and other supervisors) to pass already opened sockets to a process.
+
+use std::os::unix::io::{FromRawFd, IntoRawFd, OwnedFd, RawFd};
+use std::os::unix::net::UnixListener;
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::net::TcpListener;
+use crate::error::*;
+
+/// The first file descriptor passed by the supervisor.
+const LISTEN_FDS_START: RawFd = 3;
+
+/// Whether the inherited file descriptors were already taken.
+static TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// Listeners inherited from the supervisor.
+#[derive(Debug, Default)]
+pub(crate) struct Inherited {
+    pub(crate) tcp: Vec<TcpListener>,
+    pub(crate) unix: Vec<UnixListener>,
+}
+
+/// Takes ownership of the listeners passed by the supervisor.
+/// 
+/// Returns no listeners if the process was not socket activated,
+/// or if the listeners were already taken.
+pub(crate) fn inherited() -> Result<Inherited> {
+    let mut inherited = Inherited::default();
+    let Some(count) = listen_fds()? else {
+        log::debug!("No sockets were passed by the supervisor");
+        return Ok(inherited);
+    };
+    if TAKEN.swap(true, Ordering::SeqCst) {
+        log::warn!("Inherited sockets were already taken");
+        return Ok(inherited);
+    }
+    for fd in LISTEN_FDS_START..LISTEN_FDS_START + count {
+        if !is_listening_stream(fd) {
+            log::warn!("Ignoring inherited file descriptor {}, as it is not a listening stream socket", fd);
+            continue;
+        }
+        // SAFETY: the supervisor passed us these descriptors, and the `TAKEN`
+        // flag guarantees that they are only ever owned once.
+        let tcp = TcpListener::from(unsafe { OwnedFd::from_raw_fd(fd) });
+        if let Ok(addr) = tcp.local_addr() {
+            log::debug!("Inherited a socket bound to {}", addr);
+            inherited.tcp.push(tcp);
+            continue;
+        }
+        let unix = UnixListener::from(OwnedFd::from(tcp));
+        if let Ok(addr) = unix.local_addr() {
+            log::debug!("Inherited a socket bound to {:?}", addr);
+            inherited.unix.push(unix);
+            continue;
+        }
+        // Not ours to close, leave it open.
+        let _ = OwnedFd::from(unix).into_raw_fd();
+        log::warn!("Ignoring inherited file descriptor {} of an unsupported type", fd);
+    }
+    Ok(inherited)
+}
+
+/// Reads the number of passed file descriptors, if they were meant for this process.
+/// 
+/// The environment is left untouched, as it can't be safely modified once
+/// the runtime threads are running. Child processes ignore the variables,
+/// since `LISTEN_PID` doesn't match their own process id.
+fn listen_fds() -> Result<Option<RawFd>> {
+    let (Ok(pid), Ok(fds)) = (std::env::var("LISTEN_PID"), std::env::var("LISTEN_FDS")) else {
+        return Ok(None);
+    };
+    let invalid = |what| IoError::new(IoErrorKind::InvalidInput, what);
+    let pid = pid.parse::<u32>().map_err(|_| invalid("Invalid LISTEN_PID"))?;
+    if pid != std::process::id() {
+        return Ok(None);
+    }
+    let fds = fds.parse::<RawFd>().map_err(|_| invalid("Invalid LISTEN_FDS"))?;
+    Ok(Some(fds).filter(|&fds| fds > 0))
+}
+
+/// Whether the file descriptor is a stream socket accepting connections
+/// (and not e.g. a UDP or an already connected socket).
+fn is_listening_stream(fd: RawFd) -> bool {
+    let option = |name| {
+        let mut value: libc::c_int = 0;
+        let mut len = core::mem::size_of::<libc::c_int>() as libc::socklen_t;
+        // SAFETY: `value` and `len` are valid for writes, and describe an integer option.
+        let res = unsafe {
+            libc::getsockopt(fd, libc::SOL_SOCKET, name, (&mut value as *mut libc::c_int).cast(), &mut len)
+        };
+        (res == 0).then_some(value)
+    };
+    option(libc::SO_TYPE) == Some(libc::SOCK_STREAM) && option(libc::SO_ACCEPTCONN).is_some_and(|v| v != 0)
+}
@@ -0,0 +1,72 @@
+//! Liveness and readiness of Catalyzer applications.
+
+use core::sync::atomic::{AtomicBool, Ordering};
+use axum::http::StatusCode;
+use std::sync::Arc;
+use core::fmt;
+
+/// The path of the liveness endpoint.
+pub(crate) const LIVE_PATH: &str = "/health/live";
+/// The path of the readiness endpoint.
+pub(crate) const READY_PATH: &str = "/health/ready";
+/// The handler name of the health check routes.
+pub(crate) const HANDLER: &str = "health_checks";
+
+/// A handle to the readiness of the application.
+///
+/// The application starts out ready, and becomes unready as soon as
+/// it is asked to shut down (e.g. on `SIGTERM`), before it stops accepting connections.
+/// It can also be toggled manually (e.g. while warming up caches).
+///
+/// The readiness is reported by the `/health/ready` endpoint,
+/// see [`App::health_checks`](crate::App::health_checks).
+///
+/// # Example
+///
+/// ```rust
+/// # use catalyzer::*;
+/// let app: App = App::new().health_checks();
+/// let readiness = app.readiness();
+/// readiness.set_ready(false);// Warming up...
+/// assert!(!readiness.is_ready());
+/// ```
+#[derive(Clone)]
+pub struct Readiness(Arc<AtomicBool>);
+
+impl Readiness {
+    /// Creates a new, ready `Readiness`.
+    pub(crate) fn new() -> Self {
+        Self(Arc::new(AtomicBool::new(true)))
+    }
+    /// Marks the application as ready, or not ready, to receive traffic.
+    #[inline]
+    pub fn set_ready(&self, ready: bool) {
+        self.0.store(ready, Ordering::SeqCst);
+    }
+    /// Returns `true` if the application is ready to receive traffic.
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+impl fmt::Debug for Readiness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Readiness")
+            .field(&self.is_ready())
+            .finish()
+    }
+}
+
+/// Answers the liveness probe.
+pub(crate) async fn live() -> (StatusCode, &'static str) {
+    (StatusCode::OK, "OK")
+}
+
+/// Answers the readiness probe.
+pub(crate) fn ready(readiness: &Readiness) -> (StatusCode, &'static str) {
+    match readiness.is_ready() {
+        true => (StatusCode::OK, "OK"),
+        false => (StatusCode::SERVICE_UNAVAILABLE, "Not ready"),
+    }
+}
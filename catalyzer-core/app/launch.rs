@@ -1,52 +1,216 @@
-//! 
+//! Launching of Catalyzer applications.
 
-use core::convert::Infallible;
 use core::future::*;
 use core::pin::Pin;
 
-use axum::serve::*;
-use tower::Service;
-
-use crate::res::*;
-use crate::req::*;
+use super::shutdown::ShutdownHandle;
+use super::health::Readiness;
+use core::time::Duration;
+use super::serve::*;
 use super::*;
 
 type F1 = Pin<Box<dyn Future<Output = ()> + Send>>;
-type Ca<S, State> = WithGracefulShutdown<AxumRouter<State>, S, F1>;
+type F2 = Pin<Box<dyn Future<Output = Result> + Send>>;
 
 /// A Catalyzed application that is ready to be launched.
-#[repr(transparent)]
 #[allow(missing_debug_implementations)]
-pub struct CatalyzedApp<S, State = ()>(Ca<S, State>) where
-    State: Clone + Send + Sync + 'static,
-    AxumRouter<State>: for<'a> Service<IncomingStream<'a>, Error = Infallible, Response = S> + Send + 'static,
-    for<'a> <AxumRouter<State> as Service<IncomingStream<'a>>>::Future: Send,
-    S: Service<RawRequest, Response = RawResponse, Error = Infallible> + Clone + Send + 'static,
-    S::Future: Send;
-impl<S, State> IntoFuture for CatalyzedApp<S, State> where
-    State: Clone + Send + Sync + 'static,
-    AxumRouter<State>: for<'a> Service<IncomingStream<'a>, Error = Infallible, Response = S> + Send + 'static,
-    for<'a> <AxumRouter<State> as Service<IncomingStream<'a>>>::Future: Send,
-    S: Service<RawRequest, Response = RawResponse, Error = Infallible> + Clone + Send + 'static,
-    S::Future: Send, {
-    type Output = <Ca<S, State> as IntoFuture>::Output;
-    type IntoFuture = <Ca<S, State> as IntoFuture>::IntoFuture;
-    #[inline] fn into_future(self) -> Self::IntoFuture { self.0.into_future() } }
-impl<S, State> App<State> where
-    State: Clone + Send + Sync + 'static,
-    AxumRouter<State>: for<'a> Service<IncomingStream<'a>, Error = Infallible, Response = S> + Send + 'static,
-    for<'a> <AxumRouter<State> as Service<IncomingStream<'a>>>::Future: Send,
-    S: Service<RawRequest, Response = RawResponse, Error = Infallible> + Clone + Send + 'static,
-    S::Future: Send,
-{
-    /// Catalyzes the application and launches it.
+pub struct CatalyzedApp {
+    local_addrs: Vec<SocketAddr>,
+    listeners: Vec<F2>,
+    handle: ShutdownHandle,
+    readiness: Readiness,
+    pre_stop_delay: Duration,
+    signal: F1,
+}
+
+impl CatalyzedApp {
+    /// Returns the addresses the application is actually listening on.
+    /// 
+    /// This is useful when binding to port `0`, to find out which port was assigned by the OS.
+    /// 
+    /// The plain HTTP listeners come first, in the order they were bound,
+    /// followed by the HTTPS listener. Unix domain sockets are not included.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # async fn run() -> Result {
+    /// let app: App = App::new().bind("127.0.0.1:0")?;
+    /// let app = app.launch().await?;
+    /// let addr = app.local_addrs()[0];
+    /// assert_ne!(addr.port(), 0);
+    /// # Ok(())
+    /// # }
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(run()).unwrap();
+    /// ```
+    #[inline]
+    pub fn local_addrs(&self) -> &[SocketAddr] {
+        &self.local_addrs
+    }
+    /// Returns a handle that can trigger a graceful shutdown of the application.
     /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # async fn run() -> Result {
+    /// let app: App = App::new().bind("127.0.0.1:0")?;
+    /// let app = app.launch().await?;
+    /// let handle = app.shutdown_handle();
+    /// let server = tokio::spawn(async move { app.await });
+    /// handle.shutdown();
+    /// server.await.unwrap()?;
+    /// # Ok(())
+    /// # }
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(run()).unwrap();
+    /// ```
+    #[inline]
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.handle.clone()
+    }
+}
+
+impl IntoFuture for CatalyzedApp {
+    type Output = Result;
+    type IntoFuture = F2;
+    fn into_future(self) -> Self::IntoFuture {
+        let Self { listeners, handle, readiness, pre_stop_delay, signal, .. } = self;
+        Box::pin(async move {
+            let listeners = listeners.into_iter()
+                .map(tokio::spawn)
+                .collect::<Vec<_>>();
+            let trigger = handle.clone();
+            tokio::spawn(async move {
+                tokio::select! {
+                    _ = signal => {
+                        readiness.set_ready(false);
+                        if !pre_stop_delay.is_zero() {
+                            log::info!("Marked as not ready, shutting down in {:?}...", pre_stop_delay);
+                            tokio::time::sleep(pre_stop_delay).await;
+                        }
+                        trigger.shutdown();
+                    },
+                    _ = trigger.wait() => readiness.set_ready(false),
+                }
+            });
+            for listener in listeners {
+                listener.await.map_err(IoError::other)??;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl App {
+    /// Catalyzes the application and launches it.
+    ///
     /// This should be the last method called on the [`App`](crate::App) instance.
-    pub async fn launch(self) -> Result<CatalyzedApp<S, State>> {
-        let addr = self.address.ok_or(CatalyzerError::NoAddress)?;
-        let tcp = tokio::net::TcpListener::bind(addr).await?;
-        let app = axum::serve(tcp, self.router);
-        Ok(CatalyzedApp(app.with_graceful_shutdown(signal_handler())))
+    pub async fn launch(mut self) -> Result<CatalyzedApp> {
+        if let Some(e) = self.deferred_error.take() {
+            return Err(e);
+        }
+        if !self.has_listeners() {
+            return Err(CatalyzerError::NoAddress);
+        }
+        self.mount_openapi()?;
+        self.mount_routes();
+        if self.log_routes {
+            log::info!("Mounted routes:\n{}", super::routing::listing(self.routes.iter()).trim_end());
+        }
+        #[cfg(debug_assertions)]
+        if !self.routes.has_fallback() {
+            self.router = self.router.fallback(super::routing::not_found_page(&self.routes));
+        }
+        if let Some(renderer) = self.problem_renderer.clone() {
+            self.router = self.router.layer(axum::middleware::map_response(move |res| {
+                let renderer = renderer.clone();
+                async move { renderer.render(res) }
+            }));
+        }
+        let stopped = self.shutdown.subscribe();
+        let mut local_addrs = Vec::new();
+        let mut listeners: Vec<F2> = Vec::new();
+        #[cfg(feature = "tls")]
+        let mut https = None;
+        #[cfg(feature = "tls")]
+        if let (Some(addr), Some(tls)) = (self.https_address, &self.tls) {
+            use super::tls::*;
+            let config = ReloadableConfig::new(tls.load().await?);
+            let reload = reload_on_hangup(tls.clone(), config.clone(), stopped.clone());
+            let tcp = tokio::net::TcpListener::bind(addr).await?;
+            let addr = tcp.local_addr()?;
+            log::info!("Listening on https://{}", addr);
+            https = Some(addr);
+            let tls = TlsListener { tcp, config };
+            listeners.push(Box::pin(serve(tls, self.router.clone(), stopped.clone(), self.drain_timeout)));
+            listeners.push(Box::pin(reload));
+        }
+        let mut plain = Vec::new();
+        for &addr in &self.addresses {
+            plain.push(tokio::net::TcpListener::bind(addr).await?);
+        }
+        for tcp in self.tcp_listeners {
+            tcp.set_nonblocking(true)?;
+            plain.push(tokio::net::TcpListener::from_std(tcp)?);
+        }
+        for tcp in plain {
+            let addr = tcp.local_addr()?;
+            log::info!("Listening on http://{}", addr);
+            local_addrs.push(addr);
+            #[cfg(feature = "tls")]
+            if let (true, Some(https)) = (self.redirect_https, https) {
+                log::debug!("Redirecting HTTP to HTTPS on {}", https);
+                let redirect = super::tls::https_redirect(https);
+                listeners.push(Box::pin(serve(tcp, redirect, stopped.clone(), self.drain_timeout)));
+                continue;
+            }
+            listeners.push(Box::pin(serve(tcp, self.router.clone(), stopped.clone(), self.drain_timeout)));
+        }
+        #[cfg(feature = "tls")]
+        local_addrs.extend(https);
+        #[cfg(unix)]
+        for socket in self.unix_sockets {
+            let unix = socket.bind()?;
+            log::info!("Listening on unix:{}", socket.path().display());
+            let router = self.router.clone();
+            let stopped = stopped.clone();
+            let drain_timeout = self.drain_timeout;
+            listeners.push(Box::pin(async move {
+                let res = serve(unix, router, stopped, drain_timeout).await;
+                socket.cleanup();
+                res
+            }));
+        }
+        #[cfg(unix)]
+        for unix in self.unix_listeners {
+            unix.set_nonblocking(true)?;
+            let unix = tokio::net::UnixListener::from_std(unix)?;
+            match unix.local_addr()?.as_pathname() {
+                Some(path) => log::info!("Listening on unix:{}", path.display()),
+                None => log::info!("Listening on an unnamed Unix socket"),
+            }
+            listeners.push(Box::pin(serve(unix, self.router.clone(), stopped.clone(), self.drain_timeout)));
+        }
+        let signal = self.shutdown_signal.map_or_else(signal_handler, |s| s.0);
+        Ok(CatalyzedApp {
+            local_addrs,
+            listeners,
+            handle: self.shutdown,
+            readiness: self.readiness,
+            pre_stop_delay: self.pre_stop_delay,
+            signal,
+        })
+    }
+    /// Whether the application has anything to listen on.
+    fn has_listeners(&self) -> bool {
+        #[cfg(unix)]
+        if !self.unix_sockets.is_empty() || !self.unix_listeners.is_empty() {
+            return true;
+        }
+        !self.addresses.is_empty()
+            || !self.tcp_listeners.is_empty()
+            || self.https_address.is_some()
     }
 }
 
@@ -7,6 +7,20 @@ use std::net::{SocketAddr, ToSocketAddrs};
 use axum::Router as AxumRouter;
 
 pub(crate) mod launch;
+mod serve;
+#[cfg(feature = "tls")]
+pub(crate) mod tls;
+#[cfg(unix)]
+pub(crate) mod unix;
+#[cfg(unix)]
+mod activation;
+pub(crate) mod shutdown;
+pub(crate) mod health;
+pub(crate) mod routing;
+pub(crate) mod openapi;
+
+/// How long to wait for in-flight connections by default, see [`App::drain_timeout`].
+const DEFAULT_DRAIN_TIMEOUT: core::time::Duration = core::time::Duration::from_secs(30);
 
 /// The main application type.
 /// 
@@ -14,8 +28,36 @@ pub(crate) mod launch;
 #[derive(Debug)]
 pub struct App<State = ()> {
     router: AxumRouter<State>,
-    address: Option<SocketAddr>,
+    routes: routing::RouteTable<State>,
+    addresses: Vec<SocketAddr>,
+    tcp_listeners: Vec<std::net::TcpListener>,
     https_address: Option<SocketAddr>,
+    #[cfg(feature = "tls")]
+    tls: Option<tls::TlsConfig>,
+    #[cfg(feature = "tls")]
+    redirect_https: bool,
+    #[cfg(unix)]
+    unix_sockets: Vec<unix::UnixSocketConfig>,
+    #[cfg(unix)]
+    unix_listeners: Vec<std::os::unix::net::UnixListener>,
+    shutdown: shutdown::ShutdownHandle,
+    shutdown_signal: Option<shutdown::ShutdownSignal>,
+    readiness: health::Readiness,
+    pre_stop_delay: core::time::Duration,
+    drain_timeout: core::time::Duration,
+    log_routes: bool,
+    openapi: openapi::OpenApiConfig,
+    problem_renderer: Option<crate::res::ProblemRenderer>,
+    deferred_error: Option<CatalyzerError>,
+}
+
+impl<State> Default for App<State> where
+    State: Clone + Send + Sync + 'static
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<State> App<State> where
@@ -32,8 +74,27 @@ impl<State> App<State> where
     pub fn new() -> Self {
         Self {
             router: AxumRouter::<State>::new(),
-            address: None,
+            routes: routing::RouteTable::default(),
+            addresses: Vec::new(),
+            tcp_listeners: Vec::new(),
             https_address: None,
+            #[cfg(feature = "tls")]
+            tls: None,
+            #[cfg(feature = "tls")]
+            redirect_https: false,
+            #[cfg(unix)]
+            unix_sockets: Vec::new(),
+            #[cfg(unix)]
+            unix_listeners: Vec::new(),
+            shutdown: shutdown::ShutdownHandle::new(),
+            shutdown_signal: None,
+            readiness: health::Readiness::new(),
+            pre_stop_delay: core::time::Duration::ZERO,
+            drain_timeout: DEFAULT_DRAIN_TIMEOUT,
+            log_routes: false,
+            openapi: openapi::OpenApiConfig::default(),
+            problem_renderer: None,
+            deferred_error: None,
         }
     }
     /// Mounts a route handler on the application.
@@ -42,21 +103,54 @@ impl<State> App<State> where
     /// Additionally, you need to provide a metadata type that implements the
     /// [`HandlerMetadata`] trait.
     /// 
+    /// Any valid method can be handled, including extension methods (e.g. WebDAV's `PROPFIND`).
+    /// 
+    /// Mounting two handlers on the same method and path returns a
+    /// [`ConflictingRoutes`](crate::internals::InnerCatalyzerError::ConflictingRoutes) error,
+    /// and paths that only differ by their parameter names (e.g. `/users/{id}` and `/users/{user_id}`) a
+    /// [`MismatchedParameters`](crate::internals::InnerCatalyzerError::MismatchedParameters) error.
+    /// 
     /// # Example
     /// 
     /// ```rust
     /// # use catalyzer::*;
-    /// # #[main]
+    /// # use macros::*;
     /// # fn main() -> Result {
     /// #[get("/")]
     /// fn index() {
     ///     "Hello, world!"
     /// }
     /// 
-    /// let app = App::new()
+    /// let app: App = App::new()
     ///     .route::<_, index_metadata, _>(index)?;
+    /// # Ok(())
     /// # }
+    /// ```
+    /// 
+    /// Conflicting handlers:
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # use macros::*;
+    /// # fn main() -> Result {
+    /// #[get("/")]
+    /// fn a() { "a" }
+    /// #[get("/")]
+    /// fn b() { "b" }
     /// 
+    /// let app: App = App::new().route::<_, a_metadata, _>(a)?;
+    /// assert!(app.route::<_, b_metadata, _>(b).is_err());
+    /// 
+    /// #[get("/users/{id}")]
+    /// fn user(id: u32) { "User" }
+    /// #[delete("/users/{user_id}")]
+    /// fn remove(user_id: u32) { "Removed" }
+    /// 
+    /// let app: App = App::new().route::<_, user_metadata, _>(user)?;
+    /// assert!(app.route::<_, remove_metadata, _>(remove).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn route<Return, Meta, Handler>(
         mut self,
         handler: Handler
@@ -65,46 +159,611 @@ impl<State> App<State> where
         Meta: HandlerMetadata,
         Return: 'static
     {
-        let method_router = match Meta::METHOD {
-            Method::GET => axum::routing::get(handler),
-            Method::POST => axum::routing::post(handler),
-            Method::PUT => axum::routing::put(handler),
-            Method::DELETE => axum::routing::delete(handler),
-            Method::PATCH => axum::routing::patch(handler),
-            Method::HEAD => axum::routing::head(handler),
-            Method::OPTIONS => axum::routing::options(handler),
-            Method::TRACE => axum::routing::trace(handler),
-            _ => return Err(crate::CatalyzerError::UnsupportedMethodError)
+        if Meta::FALLBACK {
+            log::trace!("Mounted `{}` as the fallback", Meta::NAME);
+            self.routes.set_fallback(Some(Meta::NAME), axum::routing::any(handler))?;
+            return Ok(self);
+        }
+        let info = routing::HandlerInfo::of::<Meta>();
+        self.insert_route(info, Meta::PATH, Meta::METHODS, |method| {
+            Ok(routing::endpoint(method, handler.clone()))
+        })?;
+        Ok(self)
+    }
+    /// Sets the handler for requests no route matches.
+    /// 
+    /// Handlers marked with `#[fallback]` can also be mounted with [`route`](App::route)
+    /// (or the [`App!`](macro.App.html) macro).
+    /// 
+    /// Without a fallback, debug builds answer with a 404 page listing the mounted routes,
+    /// and release builds with an empty `404 Not Found`.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// let app: App = App::new()
+    ///     .fallback(|| async { "Nothing to see here" });
+    /// ```
+    pub fn fallback<Return, Handler>(mut self, handler: Handler) -> Self where
+        Handler: AxumHandler<Return, State>,
+        Return: 'static
+    {
+        log::trace!("Mounted a fallback");
+        // Only `#[fallback]` handlers can conflict, so this can't fail.
+        let _ = self.routes.set_fallback(None, axum::routing::any(handler));
+        self
+    }
+    /// Mounts all route handlers in the program.
+    /// 
+    /// Every handler created with the `#[get]`, `#[post]`, etc. macros is registered automatically,
+    /// so new handlers can't be forgotten. This is what `App![*]` expands to.
+    /// 
+    /// Handlers are mounted if they don't use a state, or if their [`State`](crate::req::State)
+    /// extractor uses the exact state type of the application.
+    /// Conflicts are reported as errors, like with [`route`](App::route).
+    /// 
+    /// This requires the `registry` feature.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # use macros::*;
+    /// # fn main() -> Result {
+    /// #[get("/")]
+    /// fn index() { "Hello, world!" }
+    /// 
+    /// let app: App = App::new().with_all_routes()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "registry")]
+    pub fn with_all_routes(mut self) -> Result<Self> {
+        use crate::internals::registry::{self, RegisteredHandler};
+        use core::any::TypeId;
+        let state = TypeId::of::<State>();
+        for handler in registry::handlers() {
+            let RegisteredHandler { name, path, methods, fallback, location, operation, .. } = *handler;
+            let required = (handler.state)();
+            if required != state && required != TypeId::of::<()>() {
+                log::debug!("Skipping `{}`, as it requires a different state", name);
+                continue;
+            }
+            let endpoint = |method: Option<&Method>| {
+                let endpoint = (handler.endpoint)(method);
+                match required == state {
+                    true => registry::downcast::<State>(endpoint),
+                    false => registry::downcast::<()>(endpoint).map(|e| e.with_state(())),
+                }.ok_or_else(|| Inner::InvalidRegisteredHandler(name).into())
+            };
+            if fallback {
+                log::trace!("Mounted `{}` as the fallback", name);
+                self.routes.set_fallback(Some(name), endpoint(None)?)?;
+                continue;
+            }
+            let handler = routing::HandlerInfo { name, location, operation };
+            self.insert_route(handler, path, methods, |method| endpoint(Some(method)))?;
+        }
+        Ok(self)
+    }
+    /// Adds a route for every method of a handler.
+    fn insert_route<F>(
+        &mut self,
+        handler: routing::HandlerInfo,
+        path: &'static str,
+        methods: &'static [&'static str],
+        mut endpoint: F,
+    ) -> Result where
+        F: FnMut(&Method) -> Result<axum::routing::MethodRouter<State>>,
+    {
+        if methods.is_empty() {
+            return Err(crate::CatalyzerError::UnsupportedMethodError);
+        }
+        for &method in methods {
+            let method = Method::from_bytes(method.as_bytes())
+                .map_err(|_| crate::CatalyzerError::UnsupportedMethodError)?;
+            let endpoint = endpoint(&method)?;
+            let route = routing::Route {
+                method,
+                path: path.to_string(),
+                handler: handler.name,
+                location: handler.location,
+                operation: Some(handler.operation),
+            };
+            log::trace!("Mounted a {}", route);
+            self.routes.insert(route, endpoint)?;
+        }
+        Ok(())
+    }
+    /// Sets the handler for requests to a known path, but with a method no route of the path handles.
+    /// 
+    /// The response always has the `405 Method Not Allowed` status,
+    /// and an `Allow` header listing the methods of the path.
+    /// By default, its body is empty.
+    /// 
+    /// Paths already mounted by [`inner`](App::inner) keep the handler they were mounted with.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// let app: App = App::new()
+    ///     .method_not_allowed(|| async { "Try another method" });
+    /// ```
+    pub fn method_not_allowed<Return, Handler>(mut self, handler: Handler) -> Self where
+        Handler: AxumHandler<Return, State>,
+        Return: 'static
+    {
+        log::trace!("Mounted a method not allowed handler");
+        self.routes.set_method_not_allowed(axum::routing::any(handler));
+        self
+    }
+    /// Customizes how rejected requests are rendered, for all routes.
+    /// 
+    /// When an extractor (see [`req`](crate::req)) rejects a request, the [`Problem`](crate::res::Problem)
+    /// is passed to the renderer instead of being answered as `application/problem+json`.
+    /// The response keeps the status of the problem.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// let app: App = App::new()
+    ///     .render_rejections(|problem: res::Problem| {
+    ///         format!("{}: {}", problem.title(), problem.detail())
+    ///     });
+    /// ```
+    pub fn render_rejections<F, R>(mut self, render: F) -> Self where
+        F: Fn(crate::res::Problem) -> R + Send + Sync + 'static,
+        R: IntoRawResponse,
+    {
+        self.problem_renderer = Some(crate::res::ProblemRenderer::new(render));
+        self
+    }
+    /// Automatically answers `HEAD` and `OPTIONS` requests on every route.
+    ///
+    /// `OPTIONS` is answered with `204 No Content`, listing the allowed methods
+    /// in the `Allow` header, and `HEAD` with the headers of the `GET` handler, without the body.
+    /// Routes that handle these methods themselves are left untouched,
+    /// and the automatic routes are part of the route table, like any other route.
+    ///
+    /// Paths already mounted by [`inner`](App::inner) are left untouched.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # use macros::*;
+    /// # fn main() -> Result {
+    /// #[get("/")]
+    /// fn index() { "Hello, world!" }
+    ///
+    /// // `OPTIONS /` answers with `Allow: GET, HEAD, OPTIONS`
+    /// let app: App = App![index]
+    ///     .automatic_methods();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn automatic_methods(mut self) -> Self {
+        self.routes.set_automatic_methods(true);
+        self
+    }
+    /// Mounts all routes of another application under a common prefix.
+    /// 
+    /// Only the routes (and services) of the other application are used,
+    /// its addresses and other settings are ignored.
+    /// Conflicts with the routes of this application are reported as errors,
+    /// like with [`route`](App::route).
+    /// The prefix must start with `/`, and can't contain parameters or wildcards.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # use macros::*;
+    /// # fn main() -> Result {
+    /// #[get("/users")]
+    /// fn users() { "Users" }
+    /// #[get("/orders")]
+    /// fn orders() { "Orders" }
+    /// 
+    /// // Serves `/api/v1/users` and `/api/v1/orders`
+    /// let app: App = App::new()
+    ///     .nest("/api/v1", App![users, orders])?;
+    /// 
+    /// assert!(App::<()>::new().nest("/users/{id}", App![orders]).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// 
+    /// Nesting under the root (`/` or an empty prefix) keeps the paths as they are:
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # use macros::*;
+    /// # fn main() -> Result {
+    /// #[get("/")]
+    /// fn index() { "Index" }
+    /// #[get("/users")]
+    /// fn users() { "Users" }
+    /// 
+    /// let app: App = App::new().nest("/", App![index])?.nest("", App![users])?;
+    /// let paths = app.routes().iter().map(|r| r.path().to_string()).collect::<Vec<_>>();
+    /// assert_eq!(paths, ["/", "/users"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn nest(mut self, prefix: &str, mut other: App<State>) -> Result<Self> {
+        let prefix = prefix.trim_end_matches('/');
+        let literal = prefix.split('/').all(|s| !s.starts_with([':', '*']) && !s.contains(['{', '}']));
+        if !(prefix.is_empty() || prefix.starts_with('/')) || !literal {
+            return Err(Inner::InvalidRoutePrefix(prefix.to_string()).into());
+        }
+        log::trace!("Nested an application on \"{}\"", prefix);
+        let mut router = core::mem::take(&mut other.router);
+        if let Some(fallback) = other.routes.take_fallback() {
+            router = router.fallback(fallback);
+        }
+        self.router = match prefix {
+            "" => self.router.merge(router),
+            _ => self.router.nest(prefix, router),
         };
-        log::trace!("Mounted a {} on \"{}\"", Meta::METHOD, Meta::PATH);
-        self.router = self.router.route(Meta::PATH, method_router);
+        self.routes.nest(prefix, other.routes)?;
         Ok(self)
     }
+    /// Returns all routes mounted on the application, in the order they were added.
+    /// 
+    /// Fallbacks, services and the routes of the inner router are not included.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # use macros::*;
+    /// # fn main() -> Result {
+    /// #[route("/users/{id}", methods = [GET, DELETE])]
+    /// fn user(id: u32) { "User" }
+    /// 
+    /// let app: App = App![user];
+    /// let routes = app.routes();
+    /// assert_eq!(routes[0].method(), internals::Method::GET);
+    /// assert_eq!(routes[1].method(), internals::Method::DELETE);
+    /// assert_eq!(routes[1].path(), "/users/:id");
+    /// assert_eq!(routes[1].handler(), "user");
+    /// assert!(routes[1].location().is_some());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn routes(&self) -> Vec<routing::Route> {
+        self.routes.iter().cloned().collect()
+    }
+    /// Logs a table of all routes (see [`routes`](App::routes)) when the application is launched.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// let app: App = App::new()
+    ///     .log_routes();
+    /// ```
+    #[inline]
+    pub fn log_routes(mut self) -> Self {
+        self.log_routes = true;
+        self
+    }
     /// Binds the application to a specific address.
     /// 
     /// This is required before launching the application.
     /// 
+    /// All addresses the provided address resolves to are bound
+    /// (e.g. both `127.0.0.1` and `::1` for `localhost`), and this method can
+    /// be called several times to listen on more addresses.
+    /// All of them are served by the same router.
+    /// 
     /// # Example
     /// 
     /// ```rust
     /// # use catalyzer::*;
-    /// # #[main]
     /// # fn main() -> Result {
-    /// let app = App::new().bind("0.0.0.0:8080")?;// Localhost on port 8080
+    /// let app: App = App::new().bind("0.0.0.0:8080")?;// Localhost on port 8080
+    /// # Ok(())
     /// # }
+    /// ```
+    /// 
+    /// Listening on multiple addresses:
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # fn main() -> Result {
+    /// let app: App = App::new()
+    ///     .bind("127.0.0.1:3000")?
+    ///     .bind("[::1]:3000")?
+    ///     .bind("127.0.0.1:9000")?;// An admin port
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn bind<Addr>(mut self, addr: Addr) -> Result<Self> where
         Addr: ToSocketAddrs
+    {
+        let addrs = addr.to_socket_addrs()?.collect::<Vec<_>>();
+        if addrs.is_empty() {
+            return Err(IoError::new(
+                IoErrorKind::AddrNotAvailable,
+                "No addresses found for the provided address"
+            ).into());
+        }
+        for addr in addrs {
+            if self.addresses.contains(&addr) { continue; }
+            log::debug!("Binding to {}", addr);
+            self.addresses.push(addr);
+        }
+        Ok(self)
+    }
+    /// Serves the application on an already opened listener.
+    /// 
+    /// This is useful when the socket is opened by someone else
+    /// (e.g. a supervisor holding it across restarts).
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # fn main() -> Result {
+    /// let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    /// let app: App = App::new().listen(listener);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn listen(mut self, listener: std::net::TcpListener) -> Self {
+        match listener.local_addr() {
+            Ok(addr) => log::debug!("Listening on a socket bound to {}", addr),
+            Err(_) => log::debug!("Listening on a pre-opened socket"),
+        }
+        self.tcp_listeners.push(listener);
+        self
+    }
+    /// Serves the application on the sockets passed by the supervisor.
+    /// 
+    /// This implements the `LISTEN_FDS` / `LISTEN_PID` socket activation protocol
+    /// used by systemd. Both TCP and Unix domain sockets are supported,
+    /// other file descriptors (e.g. UDP sockets) are ignored.
+    /// If the process was not socket activated, the application is left unchanged.
+    /// 
+    /// The `LISTEN_*` variables are left in the environment, child processes ignore them
+    /// because `LISTEN_PID` doesn't match their process id.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # fn main() -> Result {
+    /// let app: App = App::new().socket_activation()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn socket_activation(mut self) -> Result<Self> {
+        let inherited = activation::inherited()?;
+        self.tcp_listeners.extend(inherited.tcp);
+        self.unix_listeners.extend(inherited.unix);
+        Ok(self)
+    }
+    /// Binds the application to a specific address, serving HTTPS on it.
+    /// 
+    /// This can be used alongside, or instead of, [`bind`](App::bind).
+    /// 
+    /// On Unix, sending `SIGHUP` to the process reloads the certificate and key,
+    /// without dropping existing connections.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,no_run
+    /// # use catalyzer::*;
+    /// # fn main() -> Result {
+    /// let tls = TlsConfig::from_pem_files("cert.pem", "key.pem");
+    /// let app: App = App::new()
+    ///     .bind("0.0.0.0:80")?
+    ///     .bind_https("0.0.0.0:443", tls)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tls")]
+    pub fn bind_https<Addr>(mut self, addr: Addr, tls: tls::TlsConfig) -> Result<Self> where
+        Addr: ToSocketAddrs
     {
         let mut addrs = addr.to_socket_addrs()?;
         let addr = addrs.next().ok_or(IoError::new(
             IoErrorKind::AddrNotAvailable,
             "No addresses found for the provided address"
         ))?;
-        
-        log::debug!("Binding to {}", addr);
-        self.address = Some(addr);
+
+        log::debug!("Binding HTTPS to {}", addr);
+        self.https_address = Some(addr);
+        self.tls = Some(tls);
         Ok(self)
     }
+    /// Binds the application to a Unix domain socket.
+    /// 
+    /// This can be used alongside, or instead of, [`bind`](App::bind).
+    /// 
+    /// By default, a stale socket file left behind by a previous run is removed
+    /// on startup, and the socket file is removed on graceful shutdown.
+    /// See [`UnixSocketConfig`](crate::UnixSocketConfig) for all available options.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// let app: App = App::new()
+    ///     .bind_unix("/run/app.sock")
+    ///     .bind_unix(UnixSocketConfig::new("/run/admin.sock").mode(0o600));
+    /// ```
+    #[cfg(unix)]
+    pub fn bind_unix<C>(mut self, socket: C) -> Self where
+        C: Into<unix::UnixSocketConfig>
+    {
+        let socket = socket.into();
+        log::debug!("Binding to {}", socket.path.display());
+        self.unix_sockets.push(socket);
+        self
+    }
+    /// Redirects all plain HTTP traffic to HTTPS.
+    /// 
+    /// When both [`bind`](App::bind) and [`bind_https`](App::bind_https) are used,
+    /// the plain HTTP listeners will answer every request with a `308 Permanent Redirect`
+    /// to the same path on the HTTPS port, instead of routing it to the handlers.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust,no_run
+    /// # use catalyzer::*;
+    /// # fn main() -> Result {
+    /// let tls = TlsConfig::from_pem_files("cert.pem", "key.pem");
+    /// let app: App = App::new()
+    ///     .bind("0.0.0.0:80")?
+    ///     .bind_https("0.0.0.0:443", tls)?
+    ///     .redirect_to_https();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tls")]
+    pub fn redirect_to_https(mut self) -> Self {
+        self.redirect_https = true;
+        self
+    }
+    /// Returns a handle that can trigger a graceful shutdown of the application.
+    /// 
+    /// See [`ShutdownHandle`](crate::ShutdownHandle) for more information.
+    #[inline]
+    pub fn shutdown_handle(&self) -> shutdown::ShutdownHandle {
+        self.shutdown.clone()
+    }
+    /// Shuts the application down gracefully when the given future completes.
+    /// 
+    /// This replaces the default behaviour of shutting down on Ctrl+C and `SIGTERM`.
+    /// The application can still be shut down through its [`ShutdownHandle`](crate::ShutdownHandle).
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+    /// let app: App = App::new()
+    ///     .with_graceful_shutdown(async { let _ = rx.await; });
+    /// ```
+    pub fn with_graceful_shutdown<F>(mut self, signal: F) -> Self where
+        F: core::future::Future<Output = ()> + Send + 'static
+    {
+        self.shutdown_signal = Some(shutdown::ShutdownSignal(Box::pin(signal)));
+        self
+    }
+    /// Mounts the `/health/live` and `/health/ready` endpoints.
+    /// 
+    /// The liveness endpoint always answers `200 OK` while the application is serving.
+    /// The readiness endpoint answers `200 OK` while the application is ready,
+    /// and `503 Service Unavailable` once it is shutting down
+    /// (see [`Readiness`](crate::Readiness) and [`pre_stop_delay`](App::pre_stop_delay)).
+    /// 
+    /// Both are mounted like `GET` routes of the `health_checks` handler,
+    /// so mounting another handler on their paths is an error, reported by [`launch`](App::launch).
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # use macros::*;
+    /// # use catalyzer::internals::crates::tokio;
+    /// # fn main() -> Result {
+    /// let app: App = App::new().health_checks();
+    /// 
+    /// #[get("/health/ready")]
+    /// fn ready() { "Ready" }
+    /// 
+    /// let other: App = App![ready].health_checks();
+    /// # tokio::runtime::Runtime::new()?.block_on(async {
+    /// assert!(other.bind("127.0.0.1:0")?.launch().await.is_err());
+    /// # Ok(())
+    /// # })
+    /// # }
+    /// ```
+    pub fn health_checks(mut self) -> Self {
+        let readiness = self.readiness.clone();
+        let ready = move || {
+            let readiness = readiness.clone();
+            async move { health::ready(&readiness) }
+        };
+        let endpoints = [
+            (health::LIVE_PATH, axum::routing::get(health::live)),
+            (health::READY_PATH, axum::routing::get(ready)),
+        ];
+        for (path, endpoint) in endpoints {
+            let route = routing::Route {
+                method: Method::GET,
+                path: path.to_string(),
+                handler: health::HANDLER,
+                location: None,
+                operation: None,
+            };
+            log::trace!("Mounted a {}", route);
+            let res = self.routes.insert(route, endpoint);
+            self.defer_error(res);
+        }
+        self
+    }
+    /// Returns a handle to the readiness of the application.
+    /// 
+    /// See [`Readiness`](crate::Readiness) for more information.
+    #[inline]
+    pub fn readiness(&self) -> health::Readiness {
+        self.readiness.clone()
+    }
+    /// Sets how long to keep serving after being asked to shut down.
+    /// 
+    /// When the shutdown signal is received, the application is first marked as not ready,
+    /// so that the readiness endpoint starts answering `503 Service Unavailable`.
+    /// After the delay, the application stops accepting new connections.
+    /// This gives load balancers (e.g. a Kubernetes `Service`) time to stop routing traffic to it.
+    /// 
+    /// Shutting down through the [`ShutdownHandle`](crate::ShutdownHandle) skips the delay.
+    /// 
+    /// Defaults to no delay.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # use std::time::Duration;
+    /// let app: App = App::new()
+    ///     .health_checks()
+    ///     .pre_stop_delay(Duration::from_secs(5));
+    /// ```
+    #[inline]
+    pub fn pre_stop_delay(mut self, delay: core::time::Duration) -> Self {
+        self.pre_stop_delay = delay;
+        self
+    }
+    /// Sets how long to wait for in-flight connections once the application stopped accepting new ones.
+    /// 
+    /// Connections still open after the timeout (e.g. clients that never finish sending their request)
+    /// are abandoned, so that the application can exit.
+    /// 
+    /// Defaults to 30 seconds.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # use std::time::Duration;
+    /// let app: App = App::new()
+    ///     .drain_timeout(Duration::from_secs(10));
+    /// ```
+    #[inline]
+    pub fn drain_timeout(mut self, timeout: core::time::Duration) -> Self {
+        self.drain_timeout = timeout;
+        self
+    }
     /// Sets the state of the application.
     /// 
     /// If your application requires a state, you must set it using this method.
@@ -113,44 +772,86 @@ impl<State> App<State> where
     /// 
     /// ```rust
     /// # use catalyzer::*;
+    /// #[derive(Clone)]
     /// struct AppState {
     ///     counter: u32,
     /// }
     /// 
-    /// # #[main]
-    /// # fn main() -> Result {
-    /// let app = App::new()
+    /// let app: App = App::new()
     ///     .set_state(AppState { counter: 0 });
-    /// # }
-    pub fn set_state<S2>(self, state: State) -> App<S2> {
-        App {
-            router: self.router.with_state::<S2>(state),
-            address: self.address,
-            https_address: self.https_address,
-        }
+    /// ```
+    pub fn set_state<S2>(mut self, state: State) -> App<S2> {
+        let routes = core::mem::take(&mut self.routes).with_state(state.clone());
+        self.map_router(routes, |router| router.with_state::<S2>(state))
     }
     /// Mounts a service on the application.
     /// 
     /// This requires a service that implements the [`CatalyzerService`] trait.
+    /// The service handles every method of its path,
+    /// so mounting a handler or another service on the same path is an error,
+    /// reported by [`launch`](App::launch).
     pub fn service<S>(mut self, service: S) -> Self where
         S: CatalyzerService + Clone + Send + 'static,
         S::Response: IntoRawResponse,
         S::Future: Send + 'static,
     {
-        self.router = self.router.route_service(S::PATH, service);
+        let name = core::any::type_name::<S>();
+        log::trace!("Mounted the `{}` service on \"{}\"", name, S::PATH);
+        let res = self.routes.insert_service(S::PATH, name, axum::routing::any_service(service));
+        self.defer_error(res);
         self
     }
+    /// Keeps the first error of a method that can't return it, for [`launch`](App::launch) to report.
+    fn defer_error(&mut self, res: Result) {
+        if let Err(e) = res {
+            self.deferred_error.get_or_insert(e);
+        }
+    }
     /// Reveals the inner router of the application.
     /// 
     /// This is used for advanced use-cases where you need to access the inner
     /// router of the application (e.g. for mounting a sub-application or service).
-    pub fn inner<S2>(self, mapper: fn(AxumRouter<State>) -> AxumRouter<S2>) -> App<S2> {
+    /// 
+    /// All routes added so far are mounted first, so no other route can be added to their paths.
+    pub fn inner<S2>(mut self, mapper: fn(AxumRouter<State>) -> AxumRouter<S2>) -> App<S2> {
+        self.mount_routes();
+        let routes = core::mem::take(&mut self.routes).into_mounted();
+        self.map_router(routes, mapper)
+    }
+    /// Maps the inner router, keeping the rest of the configuration.
+    fn map_router<S2, F>(self, routes: routing::RouteTable<S2>, mapper: F) -> App<S2> where
+        F: FnOnce(AxumRouter<State>) -> AxumRouter<S2>,
+    {
         App {
             router: mapper(self.router),
-            address: self.address,
+            routes,
+            addresses: self.addresses,
+            tcp_listeners: self.tcp_listeners,
             https_address: self.https_address,
+            #[cfg(feature = "tls")]
+            tls: self.tls,
+            #[cfg(feature = "tls")]
+            redirect_https: self.redirect_https,
+            #[cfg(unix)]
+            unix_sockets: self.unix_sockets,
+            #[cfg(unix)]
+            unix_listeners: self.unix_listeners,
+            shutdown: self.shutdown,
+            shutdown_signal: self.shutdown_signal,
+            readiness: self.readiness,
+            pre_stop_delay: self.pre_stop_delay,
+            drain_timeout: self.drain_timeout,
+            log_routes: self.log_routes,
+            openapi: self.openapi,
+            problem_renderer: self.problem_renderer,
+            deferred_error: self.deferred_error,
         }
     }
+    /// Mounts the pending routes onto the router.
+    fn mount_routes(&mut self) {
+        let router = core::mem::take(&mut self.router);
+        self.router = self.routes.mount(router);
+    }
     /// Automatically configures the application.
     /// 
     /// This is only available in debug builds.
@@ -0,0 +1,442 @@
+//! OpenAPI documents of Catalyzer applications.
+//!
+//! The route macros describe every handler in its [`HandlerMetadata`](crate::internals::HandlerMetadata):
+//! the summary and description come from its doc comments, the parameters from the path
+//! and the `Query` extractor, and the bodies from the `Json`, `Form`, `String` and `Bytes` types.
+
+use serde_json::{json, Map, Value};
+use core::marker::PhantomData;
+use super::routing::Route;
+use crate::error::Inner;
+use crate::internals::*;
+
+/// The version of the OpenAPI specification documents follow.
+const OPENAPI_VERSION: &str = "3.1.0";
+
+/// A type that can be described by a JSON schema in OpenAPI documents.
+///
+/// Types without an implementation are described by the empty schema (`{}`),
+/// which accepts any value.
+///
+/// # Example
+///
+/// ```rust
+/// # use catalyzer::*;
+/// # use catalyzer::internals::crates::serde_json::{json, Value};
+/// struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// impl ApiSchema for User {
+///     fn schema() -> Value {
+///         json!({
+///             "type": "object",
+///             "properties": {
+///                 "name": String::schema(),
+///                 "age": u32::schema(),
+///             },
+///             "required": ["name", "age"],
+///         })
+///     }
+/// }
+/// ```
+pub trait ApiSchema {
+    /// Returns the JSON schema of the type.
+    fn schema() -> Value;
+}
+
+macro_rules! schemas {
+    ($($ty:ty => $schema:tt;)*) => {$(
+        impl ApiSchema for $ty {
+            #[inline]
+            fn schema() -> Value {
+                json!($schema)
+            }
+        }
+    )*};
+}
+
+schemas! {
+    bool => { "type": "boolean" };
+    u8 => { "type": "integer", "format": "uint8", "minimum": 0 };
+    u16 => { "type": "integer", "format": "uint16", "minimum": 0 };
+    u32 => { "type": "integer", "format": "uint32", "minimum": 0 };
+    u64 => { "type": "integer", "format": "uint64", "minimum": 0 };
+    u128 => { "type": "integer", "minimum": 0 };
+    usize => { "type": "integer", "minimum": 0 };
+    i8 => { "type": "integer", "format": "int8" };
+    i16 => { "type": "integer", "format": "int16" };
+    i32 => { "type": "integer", "format": "int32" };
+    i64 => { "type": "integer", "format": "int64" };
+    i128 => { "type": "integer" };
+    isize => { "type": "integer" };
+    f32 => { "type": "number", "format": "float" };
+    f64 => { "type": "number", "format": "double" };
+    char => { "type": "string", "minLength": 1, "maxLength": 1 };
+    str => { "type": "string" };
+    String => { "type": "string" };
+    () => { "type": "null" };
+    Value => {};
+}
+
+impl<T: ApiSchema + ?Sized> ApiSchema for &T {
+    #[inline]
+    fn schema() -> Value {
+        T::schema()
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for Option<T> {
+    fn schema() -> Value {
+        json!({ "anyOf": [T::schema(), { "type": "null" }] })
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for Vec<T> {
+    fn schema() -> Value {
+        json!({ "type": "array", "items": T::schema() })
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for [T] {
+    fn schema() -> Value {
+        json!({ "type": "array", "items": T::schema() })
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for std::collections::HashMap<String, T> {
+    fn schema() -> Value {
+        json!({ "type": "object", "additionalProperties": T::schema() })
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for std::collections::BTreeMap<String, T> {
+    fn schema() -> Value {
+        json!({ "type": "object", "additionalProperties": T::schema() })
+    }
+}
+
+/// Where a parameter is found in the request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiParamLocation {
+    /// A path parameter (e.g. `{id}`).
+    Path,
+    /// Query parameters, described by the schema of an object.
+    Query,
+}
+
+/// A parameter of a handler.
+#[derive(Debug, Clone, Copy)]
+pub struct ApiParam {
+    /// Name of the parameter.
+    pub name: &'static str,
+    /// Where the parameter is found.
+    pub location: ApiParamLocation,
+    /// Schema of the parameter.
+    pub schema: fn() -> Value,
+}
+
+/// A request or response body of a handler.
+#[derive(Debug, Clone, Copy)]
+pub struct ApiBody {
+    /// Content type of the body (e.g. `application/json`).
+    pub content_type: &'static str,
+    /// Schema of the body.
+    pub schema: fn() -> Value,
+}
+
+/// Finds the schema of a type, falling back to the empty schema.
+///
+/// This is used by the route macros, as `(&&SchemaProbe::<T>::NEW).schema()`.
+#[doc(hidden)]
+#[allow(missing_debug_implementations)]
+pub struct SchemaProbe<T: ?Sized>(PhantomData<T>);
+
+impl<T: ?Sized> SchemaProbe<T> {
+    #[doc(hidden)]
+    pub const NEW: Self = Self(PhantomData);
+}
+
+/// Picked for types that implement [`ApiSchema`].
+#[doc(hidden)]
+pub trait __KnownSchema {
+    fn schema(&self) -> Value;
+}
+
+impl<T: ApiSchema + ?Sized> __KnownSchema for &SchemaProbe<T> {
+    #[inline]
+    fn schema(&self) -> Value {
+        T::schema()
+    }
+}
+
+/// Picked for all other types.
+#[doc(hidden)]
+pub trait __UnknownSchema {
+    fn schema(&self) -> Value;
+}
+
+impl<T: ?Sized> __UnknownSchema for SchemaProbe<T> {
+    #[inline]
+    fn schema(&self) -> Value {
+        json!({})
+    }
+}
+
+/// The OpenAPI description of a route.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Operation {
+    id: &'static str,
+    summary: Option<&'static str>,
+    description: Option<&'static str>,
+    params: &'static [ApiParam],
+    request_body: Option<ApiBody>,
+    response_body: Option<ApiBody>,
+}
+
+impl Operation {
+    /// Describes the handler of `Meta`.
+    pub(crate) const fn of<Meta: HandlerMetadata>() -> Self {
+        Self {
+            id: Meta::OPERATION_ID,
+            summary: Meta::SUMMARY,
+            description: Meta::DESCRIPTION,
+            params: Meta::PARAMS,
+            request_body: Meta::REQUEST_BODY,
+            response_body: Meta::RESPONSE_BODY,
+        }
+    }
+}
+
+/// Settings of the OpenAPI document.
+#[derive(Debug, Clone)]
+pub(crate) struct OpenApiConfig {
+    pub(crate) title: String,
+    pub(crate) version: String,
+    /// The path to serve the document on.
+    pub(crate) path: Option<String>,
+}
+
+impl Default for OpenApiConfig {
+    fn default() -> Self {
+        Self {
+            title: "Catalyzer".to_string(),
+            version: "0.0.0".to_string(),
+            path: None,
+        }
+    }
+}
+
+/// Methods that can be described in OpenAPI documents.
+const METHODS: &[Method] = &[
+    Method::GET, Method::PUT, Method::POST, Method::DELETE,
+    Method::OPTIONS, Method::HEAD, Method::PATCH, Method::TRACE,
+];
+
+/// Generates the OpenAPI document of the routes.
+///
+/// Automatic routes and custom methods are left out.
+pub(crate) fn document<'a>(config: &OpenApiConfig, routes: impl Iterator<Item = &'a Route>) -> Value {
+    let routes = routes
+        .filter_map(|r| Some((r, r.operation?)))
+        .filter(|(r, _)| METHODS.contains(&r.method))
+        .collect::<Vec<_>>();
+    let mut paths = Map::new();
+    let mut ids = Vec::<String>::with_capacity(routes.len());
+    for &(route, operation) in &routes {
+        // Operation ids must be unique, so handlers of several methods get a suffix,
+        // and handlers sharing a name (e.g. in different modules) a number.
+        let shared = routes.iter().filter(|(_, o)| o.id == operation.id).count() > 1;
+        let base = match shared {
+            true => format!("{}_{}", operation.id, route.method.as_str().to_lowercase()),
+            false => operation.id.to_string(),
+        };
+        let mut id = base.clone();
+        let mut n = 1;
+        while ids.contains(&id) {
+            n += 1;
+            id = format!("{base}_{n}");
+        }
+        ids.push(id.clone());
+        let item = paths.entry(template(&route.path))
+            .or_insert_with(|| Value::Object(Map::new()));
+        item[route.method.as_str().to_lowercase()] = describe(&id, &operation);
+    }
+    json!({
+        "openapi": OPENAPI_VERSION,
+        "info": { "title": config.title, "version": config.version },
+        "paths": paths,
+    })
+}
+
+/// Describes an operation.
+fn describe(id: &str, operation: &Operation) -> Value {
+    let mut object = Map::new();
+    object.insert("operationId".to_string(), json!(id));
+    if let Some(summary) = operation.summary {
+        object.insert("summary".to_string(), json!(summary));
+    }
+    if let Some(description) = operation.description {
+        object.insert("description".to_string(), json!(description));
+    }
+    if !operation.params.is_empty() {
+        let params = operation.params.iter().map(|p| match p.location {
+            ApiParamLocation::Path => json!({
+                "name": p.name,
+                "in": "path",
+                "required": true,
+                "schema": (p.schema)(),
+            }),
+            ApiParamLocation::Query => json!({
+                "name": p.name,
+                "in": "query",
+                "style": "form",
+                "explode": true,
+                "schema": (p.schema)(),
+            }),
+        }).collect::<Vec<_>>();
+        object.insert("parameters".to_string(), Value::Array(params));
+    }
+    if let Some(body) = operation.request_body {
+        object.insert("requestBody".to_string(), json!({
+            "required": true,
+            "content": content(&body),
+        }));
+    }
+    let mut ok = json!({ "description": "OK" });
+    if let Some(body) = operation.response_body {
+        ok["content"] = content(&body);
+    }
+    object.insert("responses".to_string(), json!({ "200": ok }));
+    Value::Object(object)
+}
+
+/// Describes the content of a body.
+fn content(body: &ApiBody) -> Value {
+    let mut content = Map::new();
+    content.insert(body.content_type.to_string(), json!({ "schema": (body.schema)() }));
+    Value::Object(content)
+}
+
+/// Converts a path from the router syntax to an OpenAPI template (`/users/:id` to `/users/{id}`).
+fn template(path: &str) -> String {
+    let segments = path.split('/').map(|segment| match segment.strip_prefix([':', '*']) {
+        Some(name) => format!("{{{name}}}"),
+        None => segment.to_string(),
+    });
+    match segments.collect::<Vec<_>>().join("/") {
+        path if path.is_empty() => "/".to_string(),
+        path => path,
+    }
+}
+
+impl<State> super::App<State> where
+    State: Clone + Send + Sync + 'static
+{
+    /// Generates the OpenAPI 3.1 document describing the routes of the application.
+    ///
+    /// Every handler created with the route macros is described by its metadata:
+    /// its doc comments, path and query parameters, and request and response bodies.
+    /// Schemas come from the [`ApiSchema`] trait.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # use macros::*;
+    /// # fn main() -> Result {
+    /// /// Returns a user.
+    /// #[get("/users/{id}")]
+    /// fn user(id: u32) { "User" }
+    ///
+    /// let app: App = App![user].openapi_info("Users", "1.0.0");
+    /// let doc = app.openapi();
+    /// let operation = &doc["paths"]["/users/{id}"]["get"];
+    /// assert_eq!(operation["operationId"], "user");
+    /// assert_eq!(operation["summary"], "Returns a user.");
+    /// assert_eq!(operation["parameters"][0]["schema"]["type"], "integer");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Operation ids are unique across the document, even for handlers sharing a name:
+    ///
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # use macros::*;
+    /// # fn main() -> Result {
+    /// mod users {
+    ///     use macros::*;
+    ///     #[get("/users")]
+    ///     pub fn list() { "Users" }
+    /// }
+    /// mod orders {
+    ///     use macros::*;
+    ///     #[get("/orders")]
+    ///     pub fn list() { "Orders" }
+    /// }
+    ///
+    /// let app: App = App![users::list, orders::list];
+    /// let doc = app.openapi();
+    /// assert_eq!(doc["paths"]["/users"]["get"]["operationId"], "list_get");
+    /// assert_eq!(doc["paths"]["/orders"]["get"]["operationId"], "list_get_2");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn openapi(&self) -> Value {
+        document(&self.openapi, self.routes.iter())
+    }
+    /// Sets the title and version of the API, as shown in the OpenAPI document.
+    ///
+    /// Defaults to `Catalyzer` and `0.0.0`.
+    pub fn openapi_info(mut self, title: &str, version: &str) -> Self {
+        self.openapi.title = title.to_string();
+        self.openapi.version = version.to_string();
+        self
+    }
+    /// Serves the OpenAPI document (see [`openapi`](super::App::openapi)) on the given path.
+    ///
+    /// The document is generated when the application is launched,
+    /// so it describes all routes, no matter when they were mounted.
+    ///
+    /// The path must start with `/`, and can't contain parameters or wildcards.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # fn main() -> Result {
+    /// let app: App = App::new()
+    ///     .serve_openapi("/openapi.json")?;
+    /// assert!(App::<()>::new().serve_openapi("openapi.json").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn serve_openapi(mut self, path: &str) -> crate::Result<Self> {
+        let literal = path.split('/').skip(1).all(|s| !s.starts_with([':', '*']) && !s.contains(['{', '}']));
+        if !path.starts_with('/') || !literal {
+            return Err(Inner::InvalidPath(path.to_string()).into());
+        }
+        self.openapi.path = Some(path.to_string());
+        Ok(self)
+    }
+    /// Adds the route serving the OpenAPI document, if enabled.
+    pub(crate) fn mount_openapi(&mut self) -> crate::Result {
+        use axum::http::header;
+        let Some(path) = self.openapi.path.clone() else { return Ok(()); };
+        let doc = self.openapi().to_string();
+        let route = Route {
+            method: Method::GET,
+            path,
+            handler: "openapi",
+            location: None,
+            operation: None,
+        };
+        log::trace!("Mounted a {}", route);
+        self.routes.insert(route, axum::routing::get(move || {
+            let doc = doc.clone();
+            async move { ([(header::CONTENT_TYPE, "application/json")], doc) }
+        }))
+    }
+}
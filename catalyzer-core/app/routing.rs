@@ -0,0 +1,504 @@
+//! Bookkeeping of the routes mounted on Catalyzer applications.
+//!
+//! Handlers are collected in a [`RouteTable`], and only mounted onto the router
+//! when the application is finalized. This allows routes to be checked for conflicts,
+//! nested under a prefix, and grouped by path before they reach the router.
+
+use axum::routing::{MethodFilter, MethodRouter};
+use crate::internals::{HandlerMetadata, Method};
+use super::openapi::Operation;
+use crate::req::RawRequest;
+use axum::Router as AxumRouter;
+use axum::response::Response;
+use crate::error::*;
+use std::sync::Arc;
+use core::fmt;
+
+/// A route mounted on the application.
+/// 
+/// See [`App::routes`](crate::App::routes).
+#[derive(Debug, Clone)]
+pub struct Route {
+    pub(crate) method: Method,
+    pub(crate) path: String,
+    pub(crate) handler: &'static str,
+    pub(crate) location: Option<&'static str>,
+    /// The OpenAPI description of the route (`None` for automatic routes).
+    pub(crate) operation: Option<Operation>,
+}
+
+impl Route {
+    /// The method of the route.
+    #[inline]
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+    /// The path of the route, with parameters in the router syntax (e.g. `/users/:id`).
+    #[inline]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    /// The name of the handler (`automatic` for the routes of
+    /// [`App::automatic_methods`](crate::App::automatic_methods)).
+    #[inline]
+    pub fn handler(&self) -> &'static str {
+        self.handler
+    }
+    /// Where the handler is defined (e.g. `src/main.rs:12`), if known.
+    #[inline]
+    pub fn location(&self) -> Option<&'static str> {
+        self.location
+    }
+}
+
+/// The handler of a route, as described by its metadata.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct HandlerInfo {
+    pub(crate) name: &'static str,
+    pub(crate) location: Option<&'static str>,
+    pub(crate) operation: Operation,
+}
+
+impl HandlerInfo {
+    /// Describes the handler of `Meta`.
+    pub(crate) const fn of<Meta: HandlerMetadata>() -> Self {
+        Self {
+            name: Meta::NAME,
+            location: Meta::LOCATION,
+            operation: Operation::of::<Meta>(),
+        }
+    }
+}
+
+impl fmt::Display for Route {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} on \"{}\" (`{}`", self.method, self.path, self.handler)?;
+        if let Some(location) = self.location {
+            write!(f, " at {}", location)?;
+        }
+        f.write_str(")")
+    }
+}
+
+/// The routes of the application.
+pub(crate) struct RouteTable<S> {
+    /// Routes already mounted onto the router.
+    mounted: Vec<Route>,
+    /// Routes waiting to be mounted, with their endpoint.
+    pending: Vec<(Route, MethodRouter<S>)>,
+    /// The fallback waiting to be mounted.
+    fallback: Option<MethodRouter<S>>,
+    /// The name of the fallback handler, if it was mounted with the `#[fallback]` macro.
+    fallback_name: Option<&'static str>,
+    /// Whether a custom fallback was ever set.
+    has_fallback: bool,
+    /// The handler for requests with a method no route of the path handles.
+    method_not_allowed: Option<MethodRouter<S>>,
+    /// Whether `HEAD` and `OPTIONS` are answered automatically.
+    automatic_methods: bool,
+    /// Services, with their endpoint until they are mounted.
+    services: Vec<(String, &'static str, Option<MethodRouter<S>>)>,
+}
+
+impl<S> Default for RouteTable<S> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            mounted: Vec::new(),
+            pending: Vec::new(),
+            fallback: None,
+            fallback_name: None,
+            has_fallback: false,
+            method_not_allowed: None,
+            automatic_methods: false,
+            services: Vec::new(),
+        }
+    }
+}
+
+impl<S> fmt::Debug for RouteTable<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(self.iter().map(|r| (&r.method, &r.path, r.handler)))
+            .finish()
+    }
+}
+
+impl<S> RouteTable<S> {
+    /// Iterates over all routes, in the order they were added.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &Route> {
+        self.mounted.iter().chain(self.pending.iter().map(|(r, _)| r))
+    }
+    /// Fails if another handler already handles the same method and path,
+    /// if the path only differs from another one by its parameter names,
+    /// or if a service is mounted on the path.
+    fn check(&self, route: &Route) -> Result {
+        self.check_services(&route.path, route.handler)?;
+        if self.mounted.iter().any(|r| same_path(&r.path, &route.path)) {
+            return Err(Inner::MountedPath {
+                path: route.path.clone(),
+                handler: route.handler,
+            }.into());
+        }
+        let existing = self.iter().find(|r| {
+            r.method == route.method && same_path(&r.path, &route.path)
+        });
+        if let Some(existing) = existing {
+            return Err(Inner::ConflictingRoutes {
+                method: route.method.clone(),
+                path: route.path.clone(),
+                existing: existing.handler,
+                handler: route.handler,
+            }.into());
+        }
+        // The router can't mount the same path twice under different parameter names.
+        let renamed = self.iter().find(|r| r.path != route.path && same_path(&r.path, &route.path));
+        match renamed {
+            Some(existing) => Err(Inner::MismatchedParameters {
+                path: route.path.clone(),
+                existing_path: existing.path.clone(),
+                existing: existing.handler,
+                handler: route.handler,
+            }.into()),
+            None => Ok(()),
+        }
+    }
+    /// Fails if a service is already mounted on the path.
+    fn check_services(&self, path: &str, handler: &'static str) -> Result {
+        match self.services.iter().find(|(p, _, _)| same_path(p, path)) {
+            Some((_, existing, _)) => Err(Inner::ConflictingService {
+                path: path.to_string(),
+                existing,
+                handler,
+            }.into()),
+            None => Ok(()),
+        }
+    }
+    /// Whether a custom fallback was set.
+    #[inline]
+    pub(crate) fn has_fallback(&self) -> bool {
+        self.has_fallback
+    }
+    /// Sets the fallback, failing if another `#[fallback]` handler was already set.
+    pub(crate) fn set_fallback(&mut self, name: Option<&'static str>, endpoint: MethodRouter<S>) -> Result {
+        if let (Some(existing), Some(handler)) = (self.fallback_name, name) {
+            return Err(Inner::ConflictingFallbacks { existing, handler }.into());
+        }
+        self.fallback_name = name.or(self.fallback_name);
+        self.fallback = Some(endpoint);
+        self.has_fallback = true;
+        Ok(())
+    }
+    /// Sets the handler for requests with a method no route of the path handles.
+    #[inline]
+    pub(crate) fn set_method_not_allowed(&mut self, endpoint: MethodRouter<S>) {
+        self.method_not_allowed = Some(endpoint);
+    }
+    /// Sets whether `HEAD` and `OPTIONS` are answered automatically.
+    #[inline]
+    pub(crate) fn set_automatic_methods(&mut self, enabled: bool) {
+        self.automatic_methods = enabled;
+    }
+    /// Takes the fallback waiting to be mounted.
+    #[inline]
+    pub(crate) fn take_fallback(&mut self) -> Option<MethodRouter<S>> {
+        self.fallback.take()
+    }
+    /// Converts the table to another state, after all routes were mounted
+    /// (see [`App::inner`](crate::App::inner)).
+    pub(crate) fn into_mounted<S2>(self) -> RouteTable<S2> {
+        debug_assert!(self.pending.is_empty() && self.fallback.is_none());
+        RouteTable {
+            mounted: self.mounted,
+            pending: Vec::new(),
+            fallback: None,
+            fallback_name: self.fallback_name,
+            has_fallback: self.has_fallback,
+            method_not_allowed: None,
+            automatic_methods: self.automatic_methods,
+            services: self.services.into_iter().map(|(path, name, _)| (path, name, None)).collect(),
+        }
+    }
+}
+
+impl<S> RouteTable<S> where
+    S: Clone + Send + Sync + 'static
+{
+    /// Provides the state to the routes waiting to be mounted, converting the table to another state.
+    pub(crate) fn with_state<S2>(self, state: S) -> RouteTable<S2> {
+        let pending = self.pending.into_iter()
+            .map(|(route, endpoint)| (route, endpoint.with_state(state.clone())))
+            .collect();
+        let services = self.services.into_iter()
+            .map(|(path, name, endpoint)| (path, name, endpoint.map(|e| e.with_state(state.clone()))))
+            .collect();
+        RouteTable {
+            mounted: self.mounted,
+            pending,
+            fallback: self.fallback.map(|e| e.with_state(state.clone())),
+            fallback_name: self.fallback_name,
+            has_fallback: self.has_fallback,
+            method_not_allowed: self.method_not_allowed.map(|e| e.with_state(state)),
+            automatic_methods: self.automatic_methods,
+            services,
+        }
+    }
+    /// Adds a route, failing if another handler already handles the same method and path.
+    /// 
+    /// See [`endpoint`] for how to create the endpoint.
+    pub(crate) fn insert(&mut self, route: Route, endpoint: MethodRouter<S>) -> Result {
+        self.check(&route)?;
+        self.pending.push((route, endpoint));
+        Ok(())
+    }
+    /// Adds a service, failing if a handler or another service is already mounted on the path.
+    #[inline]
+    pub(crate) fn insert_service(&mut self, path: &str, name: &'static str, endpoint: MethodRouter<S>) -> Result {
+        self.push_service(path.to_string(), name, Some(endpoint))
+    }
+    /// Adds a service, with its endpoint if it isn't mounted yet.
+    fn push_service(&mut self, path: String, name: &'static str, endpoint: Option<MethodRouter<S>>) -> Result {
+        self.check_services(&path, name)?;
+        if let Some(existing) = self.iter().find(|r| same_path(&r.path, &path)) {
+            return Err(Inner::ConflictingService {
+                path,
+                existing: existing.handler,
+                handler: name,
+            }.into());
+        }
+        self.services.push((path, name, endpoint));
+        Ok(())
+    }
+    /// Adds all routes of another table under the given prefix (e.g. `/api`).
+    /// 
+    /// The routes the other table already mounted must be nested along with its router.
+    pub(crate) fn nest(&mut self, prefix: &str, other: RouteTable<S>) -> Result {
+        for (path, name, endpoint) in other.services {
+            self.push_service(join(prefix, &path), name, endpoint)?;
+        }
+        let mounted = other.mounted.into_iter()
+            .map(|route| Route { path: join(prefix, &route.path), ..route })
+            .collect::<Vec<_>>();
+        for route in &mounted {
+            self.check_services(&route.path, route.handler)?;
+            if self.iter().any(|r| same_path(&r.path, &route.path)) {
+                return Err(Inner::MountedPath {
+                    path: route.path.clone(),
+                    handler: route.handler,
+                }.into());
+            }
+        }
+        self.mounted.extend(mounted);
+        for (mut route, endpoint) in other.pending {
+            route.path = join(prefix, &route.path);
+            self.insert(route, endpoint)?;
+        }
+        Ok(())
+    }
+    /// Mounts all pending routes onto the router, grouped by path.
+    pub(crate) fn mount(&mut self, mut router: AxumRouter<S>) -> AxumRouter<S> {
+        let mut paths = Vec::<(String, Vec<(Method, MethodRouter<S>)>)>::new();
+        let mut routes = Vec::with_capacity(self.pending.len());
+        for (route, endpoint) in self.pending.drain(..) {
+            match paths.iter_mut().find(|(p, _)| *p == route.path) {
+                Some((_, endpoints)) => endpoints.push((route.method.clone(), endpoint)),
+                None => paths.push((route.path.clone(), vec![(route.method.clone(), endpoint)])),
+            }
+            routes.push(route);
+        }
+        for (path, mut endpoints) in paths {
+            let mut allowed = endpoints.iter().map(|(m, _)| m.clone()).collect::<Vec<_>>();
+            if allowed.contains(&Method::GET) && !allowed.contains(&Method::HEAD) {
+                allowed.insert(allowed.iter().position(|m| m == Method::GET).unwrap_or(0) + 1, Method::HEAD);
+            }
+            if self.automatic_methods {
+                // `HEAD` is served by the `GET` endpoint, without the body.
+                let get = routes.iter().find(|r| r.path == path && r.method == Method::GET);
+                let has_head = routes.iter().any(|r| r.path == path && r.method == Method::HEAD);
+                if let (Some(get), false) = (get, has_head) {
+                    let head = Route { method: Method::HEAD, operation: None, ..get.clone() };
+                    log::trace!("Mounted a {}", head);
+                    routes.push(head);
+                }
+                if !allowed.contains(&Method::OPTIONS) {
+                    allowed.push(Method::OPTIONS);
+                    endpoints.push((Method::OPTIONS, options_endpoint(&allowed)));
+                    let options = Route {
+                        method: Method::OPTIONS,
+                        path: path.clone(),
+                        handler: AUTOMATIC,
+                        location: None,
+                        operation: None,
+                    };
+                    log::trace!("Mounted a {}", options);
+                    routes.push(options);
+                }
+            }
+            let mut native = MethodRouter::new();
+            let mut custom = Vec::new();
+            for (method, endpoint) in endpoints {
+                match native_filter(&method) {
+                    Some(_) => native = native.merge(endpoint),
+                    None => custom.push((method, endpoint)),
+                }
+            }
+            native = native.merge(method_fallback(custom, &allowed, self.method_not_allowed.clone()));
+            router = router.route(&path, native);
+        }
+        self.mounted.extend(routes);
+        for (path, _, endpoint) in &mut self.services {
+            if let Some(endpoint) = endpoint.take() {
+                router = router.route(path, endpoint);
+            }
+        }
+        if let Some(fallback) = self.fallback.take() {
+            router = router.fallback(fallback);
+        }
+        router
+    }
+}
+
+/// The handler name of routes answered automatically.
+pub(crate) const AUTOMATIC: &str = "automatic";
+
+/// Creates the endpoint of a route for the given method.
+pub(crate) fn endpoint<H, T, S>(method: &Method, handler: H) -> MethodRouter<S> where
+    H: axum::handler::Handler<T, S>,
+    S: Clone + Send + Sync + 'static,
+    T: 'static,
+{
+    match native_filter(method) {
+        Some(filter) => axum::routing::on(filter, handler),
+        None => axum::routing::any(handler),
+    }
+}
+
+/// Formats the routes as a table, sorted by path and method.
+pub(crate) fn listing<'a>(routes: impl Iterator<Item = &'a Route>) -> String {
+    let mut routes = routes.collect::<Vec<_>>();
+    routes.sort_by(|a, b| (&a.path, a.method.as_str()).cmp(&(&b.path, b.method.as_str())));
+    let width = routes.iter().map(|r| r.path.len()).max().unwrap_or(0);
+    let mut listing = String::new();
+    for route in routes {
+        listing.push_str(&format!("    {:<8} {:<width$}  {}", route.method.as_str(), route.path, route.handler));
+        if let Some(location) = route.location {
+            listing.push_str(&format!(" ({location})"));
+        }
+        listing.push('\n');
+    }
+    if listing.is_empty() {
+        listing.push_str("    (none)\n");
+    }
+    listing
+}
+
+/// Creates the default fallback of debug builds, a 404 page listing all routes.
+#[cfg(debug_assertions)]
+pub(crate) fn not_found_page<S>(table: &RouteTable<S>) -> MethodRouter<()> {
+    let listing = listing(table.iter());
+    axum::routing::any(move |method: Method, uri: axum::http::Uri| async move {
+        let body = format!(
+            "404 Not Found\n\nNo route matches {} {}\n\nMounted routes:\n{}",
+            method, uri.path(), listing
+        );
+        (axum::http::StatusCode::NOT_FOUND, body)
+    })
+}
+
+/// Returns the filter of methods the router supports natively.
+fn native_filter(method: &Method) -> Option<MethodFilter> {
+    // axum 0.7 mounts `CONNECT` endpoints in place of `OPTIONS`, so dispatch it ourselves.
+    if method == Method::CONNECT { return None; }
+    MethodFilter::try_from(method.clone()).ok()
+}
+
+/// Creates the method fallback of a path.
+/// 
+/// It dispatches custom methods to their endpoints, and answers any other method
+/// with `405 Method Not Allowed`. Both list the allowed methods in the `Allow` header.
+fn method_fallback<S>(
+    custom: Vec<(Method, MethodRouter<S>)>,
+    allowed: &[Method],
+    not_allowed: Option<MethodRouter<S>>,
+) -> MethodRouter<S> where
+    S: Clone + Send + Sync + 'static
+{
+    use axum::http::{header, StatusCode};
+    let allow = allow_header(allowed);
+    let custom = Arc::new(custom);
+    MethodRouter::new().fallback(move |axum::extract::State(state): axum::extract::State<S>, req: RawRequest| {
+        let (custom, not_allowed, allow) = (custom.clone(), not_allowed.clone(), allow.clone());
+        async move {
+            if let Some((_, endpoint)) = custom.iter().find(|(m, _)| m == req.method()) {
+                let mut res = call(endpoint.clone(), state, req).await;
+                // The router adds its own (incomplete) `Allow` header to fallback responses,
+                // unless they already have one.
+                if !res.headers().contains_key(header::ALLOW) {
+                    res.headers_mut().insert(header::ALLOW, allow);
+                }
+                return res;
+            }
+            let mut res = match not_allowed {
+                Some(endpoint) => call(endpoint, state, req).await,
+                None => Response::default(),
+            };
+            *res.status_mut() = StatusCode::METHOD_NOT_ALLOWED;
+            res.headers_mut().insert(header::ALLOW, allow);
+            res
+        }
+    })
+}
+
+/// Creates the automatic `OPTIONS` endpoint of a path,
+/// answering with the allowed methods in the `Allow` header.
+fn options_endpoint<S>(allowed: &[Method]) -> MethodRouter<S> where
+    S: Clone + Send + Sync + 'static
+{
+    use axum::http::{header, StatusCode};
+    let allow = allow_header(allowed);
+    axum::routing::options(move || async move {
+        (StatusCode::NO_CONTENT, [(header::ALLOW, allow)])
+    })
+}
+
+/// Creates the value of the `Allow` header.
+fn allow_header(allowed: &[Method]) -> axum::http::HeaderValue {
+    use axum::http::HeaderValue;
+    let allow = allowed.iter().map(Method::as_str).collect::<Vec<_>>().join(", ");
+    HeaderValue::from_str(&allow).unwrap_or(HeaderValue::from_static(""))
+}
+
+/// Calls an endpoint with the given state.
+async fn call<S>(endpoint: MethodRouter<S>, state: S, req: RawRequest) -> Response where
+    S: Clone + Send + Sync + 'static
+{
+    use axum::response::IntoResponse;
+    use tower::ServiceExt;
+    match endpoint.with_state(state).oneshot(req).await {
+        Ok(res) => res.into_response(),
+        Err(e) => match e {},
+    }
+}
+
+/// Joins a prefix and a path (`/api` + `/users` = `/api/users`, `/api` + `/` = `/api`).
+/// 
+/// The prefix is either empty (the root), or starts with `/` and has no trailing `/`.
+fn join(prefix: &str, path: &str) -> String {
+    match (prefix, path) {
+        ("", _) => path.to_string(),
+        (_, "/") => prefix.to_string(),
+        _ => format!("{prefix}{path}"),
+    }
+}
+
+/// Whether both paths match the same requests (parameter names are irrelevant).
+fn same_path(a: &str, b: &str) -> bool {
+    fn normalize(segment: &str) -> &str {
+        match segment.chars().next() {
+            Some(':') => ":",
+            Some('*') => "*",
+            _ => segment,
+        }
+    }
+    a.split('/').map(normalize).eq(b.split('/').map(normalize))
+}
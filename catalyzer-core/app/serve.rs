@@ -0,0 +1,136 @@
+//! Connection handling for Catalyzer applications.
+
+use hyper_util::server::conn::auto::Builder as ConnectionBuilder;
+use hyper_util::server::graceful::GracefulShutdown;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::service::TowerToHyperService;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::watch::Receiver;
+use tokio::net::{TcpListener, TcpStream};
+use core::convert::Infallible;
+use core::future::Future;
+use std::time::Duration;
+use tower::Service;
+
+use crate::req::RawRequest;
+use crate::res::RawResponse;
+use crate::error::*;
+
+type Incoming = RawRequest<hyper::body::Incoming>;
+
+/// How long a connection may take to be set up (e.g. to complete the TLS handshake).
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A source of incoming connections.
+pub(crate) trait Transport: Send + 'static {
+    /// The raw stream yielded by the listener.
+    type Stream: Send + 'static;
+    /// The stream HTTP is spoken over.
+    type Io: AsyncRead + AsyncWrite + Unpin + Send + 'static;
+    /// Accepts a new raw stream.
+    fn accept(&mut self) -> impl Future<Output = IoResult<Self::Stream>> + Send;
+    /// Prepares an accepted stream for HTTP (e.g. performs the TLS handshake).
+    fn upgrade(&self, stream: Self::Stream) -> impl Future<Output = IoResult<Self::Io>> + Send + 'static;
+}
+
+type IoResult<T> = core::result::Result<T, IoError>;
+
+impl Transport for TcpListener {
+    type Stream = TcpStream;
+    type Io = TcpStream;
+    async fn accept(&mut self) -> IoResult<TcpStream> {
+        TcpListener::accept(self).await.map(|(stream, _)| stream)
+    }
+    fn upgrade(&self, stream: TcpStream) -> impl Future<Output = IoResult<TcpStream>> + Send + 'static {
+        core::future::ready(Ok(stream))
+    }
+}
+
+#[cfg(unix)]
+impl Transport for tokio::net::UnixListener {
+    type Stream = tokio::net::UnixStream;
+    type Io = tokio::net::UnixStream;
+    async fn accept(&mut self) -> IoResult<Self::Stream> {
+        tokio::net::UnixListener::accept(self).await.map(|(stream, _)| stream)
+    }
+    fn upgrade(&self, stream: Self::Stream) -> impl Future<Output = IoResult<Self::Io>> + Send + 'static {
+        core::future::ready(Ok(stream))
+    }
+}
+
+/// A TCP listener that performs a TLS handshake on every accepted connection.
+#[cfg(feature = "tls")]
+pub(crate) struct TlsListener {
+    pub(crate) tcp: TcpListener,
+    pub(crate) config: super::tls::ReloadableConfig,
+}
+
+#[cfg(feature = "tls")]
+impl Transport for TlsListener {
+    type Stream = TcpStream;
+    type Io = tokio_rustls::server::TlsStream<TcpStream>;
+    async fn accept(&mut self) -> IoResult<TcpStream> {
+        Transport::accept(&mut self.tcp).await
+    }
+    fn upgrade(&self, stream: TcpStream) -> impl Future<Output = IoResult<Self::Io>> + Send + 'static {
+        self.config.acceptor().accept(stream)
+    }
+}
+
+/// Serves connections from the given transport until a shutdown is signalled,
+/// then waits for the in-flight connections to finish, up to the drain timeout.
+pub(crate) async fn serve<T, S>(
+    mut transport: T,
+    service: S,
+    mut shutdown: Receiver<bool>,
+    drain_timeout: Duration,
+) -> Result where
+    T: Transport,
+    S: Service<Incoming, Response = RawResponse, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    let graceful = GracefulShutdown::new();
+    // Handshakes happen in their own tasks, and only connections that completed theirs
+    // are watched, so that a stalled handshake can't hold up the shutdown.
+    let (upgraded, mut ready) = tokio::sync::mpsc::unbounded_channel();
+    loop {
+        let stream = tokio::select! {
+            stream = transport.accept() => stream,
+            Some(io) = ready.recv() => {
+                let service = TowerToHyperService::new(service.clone());
+                let builder = ConnectionBuilder::new(TokioExecutor::new());
+                let connection = builder.serve_connection_with_upgrades(TokioIo::new(io), service);
+                let connection = graceful.watch(connection.into_owned());
+                tokio::spawn(async move {
+                    if let Err(e) = connection.await {
+                        log::trace!("Connection closed with an error: {}", e);
+                    }
+                });
+                continue;
+            },
+            _ = shutdown.wait_for(|stop| *stop) => break,
+        };
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                log::error!("Failed to accept a connection: {}", e);
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+        };
+        let upgrade = tokio::time::timeout(HANDSHAKE_TIMEOUT, transport.upgrade(stream));
+        let upgraded = upgraded.clone();
+        tokio::spawn(async move {
+            match upgrade.await {
+                Ok(Ok(io)) => { let _ = upgraded.send(io); },
+                Ok(Err(e)) => log::debug!("Failed to set up a connection: {}", e),
+                Err(_) => log::debug!("Failed to set up a connection within {:?}", HANDSHAKE_TIMEOUT),
+            }
+        });
+    }
+    drop(transport);
+    if tokio::time::timeout(drain_timeout, graceful.shutdown()).await.is_err() {
+        log::warn!("Connections were still open after {:?}, abandoning them", drain_timeout);
+    }
+    Ok(())
+}
@@ -0,0 +1,75 @@
+//! Graceful shutdown of Catalyzer applications.
+
+use tokio::sync::watch::{self, Receiver, Sender};
+use core::future::Future;
+use core::pin::Pin;
+use std::sync::Arc;
+use core::fmt;
+
+/// A handle that triggers a graceful shutdown of the application.
+/// 
+/// Once triggered, all listeners stop accepting new connections,
+/// and the application finishes after the in-flight requests are done.
+/// 
+/// Handles are cheap to clone, and can be obtained from both the
+/// [`App`](crate::App) and the [`CatalyzedApp`](crate::internals::CatalyzedApp).
+/// 
+/// # Example
+/// 
+/// ```rust
+/// # use catalyzer::*;
+/// let app: App = App::new();
+/// let handle = app.shutdown_handle();
+/// // Somewhere else (e.g. an admin endpoint, a test, or a watchdog)
+/// handle.shutdown();
+/// assert!(handle.is_shutdown());
+/// ```
+#[derive(Clone)]
+pub struct ShutdownHandle(Arc<Sender<bool>>);
+
+impl ShutdownHandle {
+    /// Creates a new, untriggered `ShutdownHandle`.
+    pub(crate) fn new() -> Self {
+        Self(Arc::new(watch::channel(false).0))
+    }
+    /// Triggers a graceful shutdown of the application.
+    /// 
+    /// Triggering the shutdown more than once has no additional effect.
+    #[inline]
+    pub fn shutdown(&self) {
+        self.0.send_replace(true);
+    }
+    /// Returns `true` if the shutdown was triggered.
+    #[inline]
+    pub fn is_shutdown(&self) -> bool {
+        *self.0.borrow()
+    }
+    /// Waits until the shutdown is triggered.
+    pub async fn wait(&self) {
+        let mut rx = self.subscribe();
+        let _ = rx.wait_for(|stop| *stop).await;
+    }
+    /// Subscribes to the shutdown.
+    #[inline]
+    pub(crate) fn subscribe(&self) -> Receiver<bool> {
+        self.0.subscribe()
+    }
+}
+
+impl fmt::Debug for ShutdownHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("ShutdownHandle")
+            .field(&self.is_shutdown())
+            .finish()
+    }
+}
+
+/// A future that resolves when the application should shut down.
+pub(crate) struct ShutdownSignal(pub(crate) Pin<Box<dyn Future<Output = ()> + Send>>);
+
+impl fmt::Debug for ShutdownSignal {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ShutdownSignal")
+    }
+}
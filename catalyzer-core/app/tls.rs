@@ -0,0 +1,173 @@
+//! TLS support for Catalyzer applications.
+
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::{self, ServerConfig};
+use axum::http::{header, uri::Authority, StatusCode};
+use std::net::{IpAddr, SocketAddr};
+use core::convert::Infallible;
+use core::future::Ready;
+use std::path::PathBuf;
+use axum::body::Body;
+use std::sync::{Arc, RwLock};
+use tokio_rustls::TlsAcceptor;
+use tower::Service;
+
+use crate::res::RawResponse;
+use crate::req::RawRequest;
+use crate::error::*;
+
+/// TLS configuration for an HTTPS listener.
+/// 
+/// Holds a PEM encoded certificate chain and private key,
+/// either as paths to files or as in-memory bytes.
+/// 
+/// # Example
+/// 
+/// ```rust,no_run
+/// # use catalyzer::*;
+/// # fn main() -> Result {
+/// let app: App = App::new()
+///     .bind_https("0.0.0.0:443", TlsConfig::from_pem_files("cert.pem", "key.pem"))?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TlsConfig(Source);
+
+#[derive(Debug, Clone)]
+enum Source {
+    Files { cert: PathBuf, key: PathBuf },
+    Pem { cert: Vec<u8>, key: Vec<u8> },
+}
+
+impl TlsConfig {
+    /// Creates a new `TlsConfig` from PEM encoded certificate chain and private key files.
+    /// 
+    /// The files are read when the application is launched.
+    pub fn from_pem_files<C, K>(cert: C, key: K) -> Self where
+        C: Into<PathBuf>,
+        K: Into<PathBuf>,
+    {
+        Self(Source::Files { cert: cert.into(), key: key.into() })
+    }
+    /// Creates a new `TlsConfig` from an in-memory PEM encoded certificate chain and private key.
+    pub fn from_pem<C, K>(cert: C, key: K) -> Self where
+        C: Into<Vec<u8>>,
+        K: Into<Vec<u8>>,
+    {
+        Self(Source::Pem { cert: cert.into(), key: key.into() })
+    }
+    /// Loads the certificate chain and private key, and builds a server configuration.
+    pub(crate) async fn load(&self) -> Result<Arc<ServerConfig>> {
+        let (cert, key) = match &self.0 {
+            Source::Files { cert, key } => (
+                tokio::fs::read(cert).await?,
+                tokio::fs::read(key).await?,
+            ),
+            Source::Pem { cert, key } => (cert.clone(), key.clone()),
+        };
+        let certs = rustls_pemfile::certs(&mut cert.as_slice())
+            .collect::<core::result::Result<Vec<CertificateDer>, _>>()?;
+        if certs.is_empty() {
+            return Err(rustls::Error::General("No certificates found".into()).into());
+        }
+        let key: PrivateKeyDer = rustls_pemfile::private_key(&mut key.as_slice())?
+            .ok_or(rustls::Error::General("No private key found".into()))?;
+
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let mut config = ServerConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()?
+            .with_no_client_auth()
+            .with_single_cert(certs, key)?;
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Ok(Arc::new(config))
+    }
+}
+
+/// A server configuration that can be swapped while the listener is running.
+/// 
+/// Connections that are already established keep using the configuration
+/// they were accepted with.
+#[derive(Debug, Clone)]
+pub(crate) struct ReloadableConfig(Arc<RwLock<Arc<ServerConfig>>>);
+
+impl ReloadableConfig {
+    /// Creates a new `ReloadableConfig` from the given server configuration.
+    #[inline]
+    pub(crate) fn new(config: Arc<ServerConfig>) -> Self {
+        Self(Arc::new(RwLock::new(config)))
+    }
+    /// Returns an acceptor for the current server configuration.
+    pub(crate) fn acceptor(&self) -> TlsAcceptor {
+        let config = match self.0.read() {
+            Err(poisoned) => poisoned.into_inner().clone(),
+            Ok(config) => config.clone(),
+        };
+        TlsAcceptor::from(config)
+    }
+    /// Replaces the server configuration used for new connections.
+    pub(crate) fn swap(&self, config: Arc<ServerConfig>) {
+        match self.0.write() {
+            Err(poisoned) => *poisoned.into_inner() = config,
+            Ok(mut current) => *current = config,
+        }
+    }
+}
+
+/// Reloads the TLS configuration every time a `SIGHUP` is received,
+/// until a shutdown is signalled.
+pub(crate) async fn reload_on_hangup(
+    tls: TlsConfig,
+    config: ReloadableConfig,
+    mut shutdown: tokio::sync::watch::Receiver<bool>,
+) -> Result {
+    // Created once, so that a `SIGHUP` received while reloading isn't lost.
+    let mut hangups = crate::runtime::signals::Hangups::new();
+    loop {
+        tokio::select! {
+            _ = hangups.recv() => {},
+            _ = shutdown.wait_for(|stop| *stop) => return Ok(()),
+        }
+        log::info!("Received SIGHUP, reloading TLS certificate...");
+        match tls.load().await {
+            Ok(new) => {
+                config.swap(new);
+                log::info!("TLS certificate reloaded successfully!");
+            },
+            Err(e) => log::error!("Failed to reload TLS certificate: {}", e),
+        }
+    }
+}
+
+/// Builds a service that redirects every request to the same path on the HTTPS port.
+pub(crate) fn https_redirect<B>(https: SocketAddr) -> impl Service<
+    RawRequest<B>,
+    Response = RawResponse,
+    Error = Infallible,
+    Future = Ready<core::result::Result<RawResponse, Infallible>>,
+> + Clone {
+    tower::service_fn(move |req: RawRequest<B>| {
+        let host = req.headers()
+            .get(header::HOST)
+            .and_then(|h| h.to_str().ok())
+            .and_then(|h| h.parse::<Authority>().ok())
+            .map(|a| a.host().to_string())
+            .unwrap_or_else(|| match https.ip() {
+                IpAddr::V6(ip) => format!("[{}]", ip),
+                IpAddr::V4(ip) => ip.to_string(),
+            });
+        let path = req.uri()
+            .path_and_query()
+            .map_or("/", |p| p.as_str());
+        let location = match https.port() {
+            443 => format!("https://{}{}", host, path),
+            port => format!("https://{}:{}{}", host, port, path),
+        };
+        let res = RawResponse::builder()
+            .status(StatusCode::PERMANENT_REDIRECT)
+            .header(header::LOCATION, location)
+            .body(Body::empty())
+            .unwrap_or_default();
+        core::future::ready(Ok(res))
+    })
+}
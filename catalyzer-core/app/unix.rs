@@ -0,0 +1,125 @@
+//! Unix domain socket support for Catalyzer applications.
+
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use tokio::net::UnixListener;
+use crate::error::*;
+
+/// Configuration for a Unix domain socket listener.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// # use catalyzer::*;
+/// let socket = UnixSocketConfig::new("/run/app.sock")
+///     .mode(0o660)
+///     .remove_stale(true)
+///     .remove_on_shutdown(true);
+/// let app: App = App::new().bind_unix(socket);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnixSocketConfig {
+    pub(crate) path: PathBuf,
+    mode: Option<u32>,
+    remove_stale: bool,
+    remove_on_shutdown: bool,
+}
+
+impl UnixSocketConfig {
+    /// Creates a new `UnixSocketConfig` for the given socket path.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            mode: None,
+            remove_stale: true,
+            remove_on_shutdown: true,
+        }
+    }
+    /// Sets the file permissions of the socket (e.g. `0o660`).
+    /// 
+    /// By default, the permissions are determined by the process umask.
+    #[inline]
+    pub fn mode(mut self, mode: u32) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+    /// Whether to remove a stale socket file (one nobody is listening on) on startup.
+    /// 
+    /// Enabled by default.
+    #[inline]
+    pub fn remove_stale(mut self, remove: bool) -> Self {
+        self.remove_stale = remove;
+        self
+    }
+    /// Whether to remove the socket file on graceful shutdown.
+    /// 
+    /// Enabled by default.
+    #[inline]
+    pub fn remove_on_shutdown(mut self, remove: bool) -> Self {
+        self.remove_on_shutdown = remove;
+        self
+    }
+    /// Returns the path of the socket.
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    /// Binds the socket, applying the configured options.
+    pub(crate) fn bind(&self) -> Result<UnixListener> {
+        if self.remove_stale && is_stale(&self.path) {
+            log::debug!("Removing stale socket {}", self.path.display());
+            std::fs::remove_file(&self.path)?;
+        }
+        let listener = UnixListener::bind(&self.path)?;
+        if let Some(mode) = self.mode {
+            std::fs::set_permissions(&self.path, std::fs::Permissions::from_mode(mode))?;
+        }
+        Ok(listener)
+    }
+    /// Cleans up after the listener has shut down.
+    pub(crate) fn cleanup(&self) {
+        if !self.remove_on_shutdown { return; }
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            log::warn!("Failed to remove socket {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// Checks whether the path is a socket that nobody is listening on.
+fn is_stale(path: &Path) -> bool {
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_socket() => matches!(
+            std::os::unix::net::UnixStream::connect(path),
+            Err(e) if e.kind() == IoErrorKind::ConnectionRefused
+        ),
+        _ => false,
+    }
+}
+
+impl From<PathBuf> for UnixSocketConfig {
+    #[inline]
+    fn from(path: PathBuf) -> Self {
+        Self::new(path)
+    }
+}
+
+impl From<&Path> for UnixSocketConfig {
+    #[inline]
+    fn from(path: &Path) -> Self {
+        Self::new(path)
+    }
+}
+
+impl From<String> for UnixSocketConfig {
+    #[inline]
+    fn from(path: String) -> Self {
+        Self::new(path)
+    }
+}
+
+impl From<&str> for UnixSocketConfig {
+    #[inline]
+    fn from(path: &str) -> Self {
+        Self::new(path)
+    }
+}
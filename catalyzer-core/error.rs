@@ -67,6 +67,70 @@ pub(crate) mod inner {
         UnsupportedMethodError,
         /// No address was provided.
         NoAddress,
+        /// Two `#[fallback]` handlers were mounted.
+        ConflictingFallbacks {
+            /// The name of the fallback that was mounted first.
+            existing: &'static str,
+            /// The name of the fallback that was being mounted.
+            handler: &'static str,
+        },
+        /// The provided route prefix is not valid.
+        InvalidRoutePrefix(String),
+        /// The provided path is not valid.
+        InvalidPath(String),
+        /// Two handlers were mounted on the same method and path.
+        ConflictingRoutes {
+            /// The method both handlers handle.
+            method: ::axum::http::Method,
+            /// The path both handlers are mounted on.
+            path: String,
+            /// The name of the handler that was mounted first.
+            existing: &'static str,
+            /// The name of the handler that was being mounted.
+            handler: &'static str,
+        },
+        /// Two handlers were mounted on paths that only differ by their parameter names
+        /// (e.g. `/users/{id}` and `/users/{user_id}`).
+        MismatchedParameters {
+            /// The path of the handler that was being mounted.
+            path: String,
+            /// The path of the handler that was mounted first.
+            existing_path: String,
+            /// The name of the handler that was mounted first.
+            existing: &'static str,
+            /// The name of the handler that was being mounted.
+            handler: &'static str,
+        },
+        /// A service and another handler or service were mounted on the same path.
+        ConflictingService {
+            /// The path both are mounted on.
+            path: String,
+            /// The name of the handler or service that was mounted first.
+            existing: &'static str,
+            /// The name of the handler or service that was being mounted.
+            handler: &'static str,
+        },
+        /// A handler was mounted on a path the router already mounted (see `App::inner`).
+        MountedPath {
+            /// The path the router already mounted.
+            path: String,
+            /// The name of the handler that was being mounted.
+            handler: &'static str,
+        },
+        /// A registered handler created an endpoint of an unexpected type (see `App::with_all_routes`).
+        InvalidRegisteredHandler(&'static str),
+        /// A request was rejected by an extractor (e.g. a malformed JSON body).
+        Rejected {
+            /// The status to answer with.
+            status: ::axum::http::StatusCode,
+            /// Why the request was rejected.
+            message: String,
+            /// The part of the request that caused the rejection, if known.
+            field: Option<String>,
+        },
+        /// An error occurred while setting up TLS.
+        #[cfg(feature = "tls")]
+        Tls(tokio_rustls::rustls::Error),
     }
     impl std::error::Error for CatalyzerError {}
     impl fmt::Display for CatalyzerError {
@@ -77,6 +141,34 @@ pub(crate) mod inner {
                 Self::RuntimeInitializationError => write!(f, "An error occurred while initializing the runtime"),
                 Self::UnsupportedMethodError => write!(f, "The provided method is not supported"),
                 Self::NoAddress => write!(f, "No address was provided"),
+                Self::ConflictingFallbacks { existing, handler } => write!(f,
+                    "Handlers `{}` and `{}` are both mounted as the fallback", existing, handler
+                ),
+                Self::InvalidRoutePrefix(p) => write!(f,
+                    "Invalid route prefix \"{}\", it must start with `/` and can't contain parameters or wildcards", p
+                ),
+                Self::InvalidPath(p) => write!(f,
+                    "Invalid path \"{}\", it must start with `/` and can't contain parameters or wildcards", p
+                ),
+                Self::ConflictingRoutes { method, path, existing, handler } => write!(f,
+                    "Handlers `{}` and `{}` are both mounted on {} \"{}\"", existing, handler, method, path
+                ),
+                Self::MismatchedParameters { path, existing_path, existing, handler } => write!(f,
+                    "Handlers `{}` and `{}` are mounted on \"{}\" and \"{}\", which only differ by their parameter names",
+                    existing, handler, existing_path, path
+                ),
+                Self::ConflictingService { path, existing, handler } => write!(f,
+                    "`{}` and `{}` are both mounted on \"{}\", but services can't share their path", existing, handler, path
+                ),
+                Self::MountedPath { path, handler } => write!(f,
+                    "Handler `{}` can't be mounted on \"{}\", as the path was already mounted by `App::inner`", handler, path
+                ),
+                Self::InvalidRegisteredHandler(h) => write!(f,
+                    "Handler `{}` was registered with an endpoint of an unexpected type", h
+                ),
+                Self::Rejected { message, .. } => f.write_str(message),
+                #[cfg(feature = "tls")]
+                Self::Tls(e) => write!(f, "An error occurred while setting up TLS: {}", e),
             }
         }
     }
@@ -118,6 +210,16 @@ impl CatalyzerError {
     pub fn into_inner(self) -> Inner {
         self.0
     }
+    /// Returns the status the error is answered with.
+    /// 
+    /// Rejected requests keep the status of their rejection,
+    /// all other errors are `500 Internal Server Error`.
+    pub fn status(&self) -> ::axum::http::StatusCode {
+        match &self.0 {
+            Inner::Rejected { status, .. } => *status,
+            _ => ::axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
 }
 
 impl std::error::Error for CatalyzerError {}
@@ -150,6 +252,86 @@ impl From<FromUtf8Error> for CatalyzerError {
     }
 }
 
+#[cfg(feature = "tls")]
+impl From<tokio_rustls::rustls::Error> for CatalyzerError {
+    #[inline]
+    fn from(e: tokio_rustls::rustls::Error) -> Self {
+        Self(Inner::Tls(e))
+    }
+}
+
+macro_rules! rejections {
+    ($($rejection:ident => $field:expr),*) => {$(
+        impl From<::axum::extract::rejection::$rejection> for CatalyzerError {
+            fn from(e: ::axum::extract::rejection::$rejection) -> Self {
+                let field: fn(&::axum::extract::rejection::$rejection) -> Option<String> = $field;
+                Self(Inner::Rejected { status: e.status(), message: e.body_text(), field: field(&e) })
+            }
+        }
+    )*};
+}
+
+rejections! {
+    QueryRejection => |e| message_field(&e.body_text()),
+    PathRejection => |e| path_field(e, None),
+    JsonRejection => |e| json_field(e),
+    FormRejection => |e| message_field(&e.body_text()),
+    BytesRejection => |_| None
+}
+
+/// Finds the path parameter that failed to deserialize.
+/// 
+/// Parameters deserialized into tuples are only known by their index,
+/// so their name is looked up in the route (e.g. `/users/:id`), if known.
+pub(crate) fn path_field(e: &::axum::extract::rejection::PathRejection, route: Option<&str>) -> Option<String> {
+    use ::axum::extract::rejection::PathRejection;
+    use ::axum::extract::path::ErrorKind;
+    let PathRejection::FailedToDeserializePathParams(e) = e else { return None; };
+    match e.kind() {
+        ErrorKind::ParseErrorAtKey { key, .. } => Some(key.clone()),
+        ErrorKind::InvalidUtf8InPathParam { key } => Some(key.clone()),
+        ErrorKind::ParseErrorAtIndex { index, .. } => route?.split('/')
+            .filter_map(|segment| segment.strip_prefix([':', '*']))
+            .nth(*index)
+            .map(str::to_string),
+        _ => None,
+    }
+}
+
+/// Replaces the field of a rejection.
+pub(crate) fn with_field(mut e: CatalyzerError, field: Option<String>) -> CatalyzerError {
+    if let (Inner::Rejected { field: old, .. }, Some(field)) = (&mut e.0, field) {
+        *old = Some(field);
+    }
+    e
+}
+
+/// Finds the field a deserialization error message names (e.g. "missing field `name`").
+fn message_field(message: &str) -> Option<String> {
+    ["missing field `", "unknown field `", "duplicate field `"].iter().find_map(|prefix| {
+        let (_, rest) = message.split_once(prefix)?;
+        rest.split_once('`').map(|(field, _)| field.to_string())
+    })
+}
+
+/// Finds the path of the field a JSON body failed to deserialize at (e.g. `user.name`).
+fn json_field(e: &::axum::extract::rejection::JsonRejection) -> Option<String> {
+    type PathError = serde_path_to_error::Error<serde_json::Error>;
+    let mut source = std::error::Error::source(e);
+    while let Some(error) = source {
+        if let Some(error) = error.downcast_ref::<PathError>() {
+            let path = error.path().to_string();
+            return match (path.as_str(), message_field(&error.inner().to_string())) {
+                ("." | "?", field) => field,
+                (_, Some(field)) => Some(format!("{path}.{field}")),
+                (_, None) => Some(path),
+            };
+        }
+        source = error.source();
+    }
+    None
+}
+
 impl From<Inner> for CatalyzerError {
     #[inline]
     fn from(e: Inner) -> Self {
@@ -163,8 +345,15 @@ pub type Result<T = (), E = CatalyzerError> = R<T, E>;
 
 impl ::axum::response::IntoResponse for CatalyzerError {
     fn into_response(self) -> axum::response::Response {
+        if let Inner::Rejected { status, message, field } = self.0 {
+            let problem = crate::res::Problem::new(status, message);
+            return match field {
+                Some(field) => problem.with_field(field),
+                None => problem,
+            }.into_response();
+        }
         let v = axum::http::Response::builder()
-            .status(::axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+            .status(self.status())
             .body(self.to_string().into());
         #[cfg(debug_assertions)]
         { v.unwrap_or_default() }
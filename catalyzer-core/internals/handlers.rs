@@ -2,6 +2,7 @@ pub use ::axum::handler::Handler as AxumHandler;
 pub use ::tower::Service as TowerService;
 pub use ::axum::http::Method;
 
+use crate::app::openapi::{ApiParam, ApiBody};
 use core::convert::Infallible;
 use crate::req::RawRequest;
 
@@ -16,6 +17,7 @@ use crate::req::RawRequest;
 /// 
 /// ```rust
 /// # use catalyzer::*;
+/// # use macros::*;
 /// #[get("/")]
 /// fn index() {
 ///     "Hello, world!"
@@ -34,15 +36,36 @@ use crate::req::RawRequest;
 /// #[allow(non_camel_case_types)]
 /// struct index_metadata;
 /// impl ::catalyzer::internals::HandlerMetadata for index_metadata {
+///     const NAME: &'static str = "index";
 ///     const PATH: &'static str = "/";
-///     const METHOD: ::catalyzer::internals::Method = ::catalyzer::internals::Method::GET;
+///     const METHODS: &'static [&'static str] = &["GET"];
 /// }
 /// ```
 pub trait HandlerMetadata {
-    /// Path to mount the handler on.
+    /// Name of the handler.
+    const NAME: &'static str;
+    /// Path to mount the handler on (with parameters in the router syntax, e.g. `/users/:id`).
     const PATH: &'static str;
-    /// Method to handle.
-    const METHOD: Method;
+    /// Methods to handle (e.g. `"GET"`).
+    const METHODS: &'static [&'static str];
+    /// Whether the handler is a fallback, handling requests no route matches.
+    /// 
+    /// Fallbacks ignore the path and methods.
+    const FALLBACK: bool = false;
+    /// Where the handler is defined (e.g. `src/main.rs:12`), if known.
+    const LOCATION: Option<&'static str> = None;
+    /// Identifier of the operation in OpenAPI documents.
+    const OPERATION_ID: &'static str = Self::NAME;
+    /// Short summary of the handler (the first paragraph of its doc comments).
+    const SUMMARY: Option<&'static str> = None;
+    /// Description of the handler (the rest of its doc comments).
+    const DESCRIPTION: Option<&'static str> = None;
+    /// Path and query parameters of the handler.
+    const PARAMS: &'static [ApiParam] = &[];
+    /// Body of the requests the handler accepts.
+    const REQUEST_BODY: Option<ApiBody> = None;
+    /// Body of the responses of the handler.
+    const RESPONSE_BODY: Option<ApiBody> = None;
 }
 
 /// A trait that represents a Catalyzer service.
@@ -5,12 +5,19 @@ pub mod runtime {
     pub use crate::runtime::*;
 }
 mod handlers;
+#[cfg(feature = "registry")]
+pub(crate) mod registry;
 
 pub use crate::internals::handlers::{HandlerMetadata, AxumHandler, Method, CatalyzerService, TowerService};
 pub use crate::internals::runtime::{CatalyzerRuntimeBuilder, CatalyzerRuntime};
 pub use crate::error::inner::CatalyzerIoError as InnerCatalyzerIoError;
 pub use crate::error::inner::CatalyzerError as InnerCatalyzerError;
 pub use crate::app::launch::CatalyzedApp;
+pub use crate::app::openapi::{ApiParam, ApiParamLocation, ApiBody};
+#[doc(hidden)]
+pub use crate::app::openapi::{SchemaProbe, __KnownSchema, __UnknownSchema};
+#[cfg(feature = "registry")]
+pub use crate::internals::registry::*;
 pub use ::axum::Router as AxumRouter;
 pub use ::utils::*;
 
@@ -21,4 +28,7 @@ pub mod crates {
     pub use ::axum;
     pub use ::tower;
     pub use ::tokio;
+    pub use ::serde_json;
+    #[cfg(feature = "registry")]
+    pub use ::inventory;
 }
@@ -0,0 +1,72 @@
+//! Registry of all route handlers in the program.
+//!
+//! Every handler created with the `#[get]`, `#[post]`, etc. macros registers itself here,
+//! so that [`App::with_all_routes`](crate::App::with_all_routes) can mount all of them.
+
+use axum::routing::MethodRouter;
+use core::any::{Any, TypeId};
+use crate::app::openapi::Operation;
+use crate::internals::*;
+
+/// A route handler registered by the route macros.
+#[allow(missing_debug_implementations)]
+pub struct RegisteredHandler {
+    pub(crate) name: &'static str,
+    pub(crate) path: &'static str,
+    pub(crate) methods: &'static [&'static str],
+    pub(crate) fallback: bool,
+    pub(crate) location: Option<&'static str>,
+    pub(crate) operation: Operation,
+    pub(crate) state: fn() -> TypeId,
+    /// Creates the endpoint (a `MethodRouter<State>`) for one of the methods,
+    /// or for all of them (for fallbacks).
+    pub(crate) endpoint: fn(Option<&Method>) -> Box<dyn Any + Send>,
+}
+
+::inventory::collect!(RegisteredHandler);
+
+impl RegisteredHandler {
+    /// Creates a new registration for the handler described by `Meta`,
+    /// which requires a state of type `State`.
+    #[doc(hidden)]
+    pub const fn new<Meta, State>(endpoint: fn(Option<&Method>) -> Box<dyn Any + Send>) -> Self where
+        Meta: HandlerMetadata,
+        State: 'static,
+    {
+        Self {
+            name: Meta::NAME,
+            path: Meta::PATH,
+            methods: Meta::METHODS,
+            fallback: Meta::FALLBACK,
+            location: Meta::LOCATION,
+            operation: Operation::of::<Meta>(),
+            state: TypeId::of::<State>,
+            endpoint,
+        }
+    }
+}
+
+/// Creates the endpoint of a registered handler.
+#[doc(hidden)]
+pub fn __registry_endpoint<H, T, S>(method: Option<&Method>, handler: H) -> Box<dyn Any + Send> where
+    H: AxumHandler<T, S>,
+    S: Clone + Send + Sync + 'static,
+    T: 'static,
+{
+    Box::new(match method {
+        Some(method) => crate::app::routing::endpoint::<H, T, S>(method, handler),
+        None => axum::routing::any(handler),
+    })
+}
+
+/// Returns all registered handlers, sorted by path and name.
+pub(crate) fn handlers() -> Vec<&'static RegisteredHandler> {
+    let mut handlers = ::inventory::iter::<RegisteredHandler>().collect::<Vec<_>>();
+    handlers.sort_by_key(|h| (h.path, h.name));
+    handlers
+}
+
+/// Downcasts an endpoint created by a registered handler.
+pub(crate) fn downcast<S: 'static>(endpoint: Box<dyn Any + Send>) -> Option<MethodRouter<S>> {
+    endpoint.downcast::<MethodRouter<S>>().ok().map(|e| *e)
+}
@@ -1,26 +1,95 @@
 pub(crate) use tokio::runtime::Runtime as TokioRuntime;
 use tokio::runtime::Builder as TokioRuntimeBuilder;
 use core::future::Future;
+use std::time::Duration;
+use core::pin::Pin;
+use core::fmt;
 use utils::*;
 use crate::*;
 
+/// How long to wait after the second signal before forcefully exiting.
+const DEFAULT_MERCY_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long to wait for the remaining tasks when tearing down the runtime.
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long the shutdown hooks are allowed to run for.
+const DEFAULT_HOOKS_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// A runtime for the Catalyzer framework.
 /// 
 /// You most likely won't need to use this directly,
 /// as everything is handled by the `#[main]` macro.
 #[derive(Debug)]
 pub struct CatalyzerRuntime {
-    tokio: TokioRuntime
+    tokio: TokioRuntime,
+    config: ShutdownConfig,
 }
 
 /// A builder for the [`CatalyzerRuntime`](crate::internals::runtime::CatalyzerRuntime).
 #[derive(Debug)]
 pub struct CatalyzerRuntimeBuilder {
     tokio: Option<TokioRuntime>,
+    config: ShutdownConfig,
+}
+
+#[derive(Debug)]
+struct ShutdownConfig {
+    mercy_timeout: Duration,
+    shutdown_timeout: Duration,
+    hooks_timeout: Duration,
+    force_exit: bool,
+    hooks: ShutdownHooks,
+}
+
+type ShutdownHook = Box<dyn FnOnce() -> Pin<Box<dyn Future<Output = ()> + Send>> + Send>;
+
+#[repr(transparent)]
+struct ShutdownHooks(Vec<ShutdownHook>);
+
+impl fmt::Debug for ShutdownHooks {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ShutdownHooks({})", self.0.len())
+    }
+}
+
+impl ShutdownHooks {
+    /// Runs all hooks in the order they were registered, giving up after the timeout.
+    async fn run(self, timeout: Duration) {
+        if self.0.is_empty() { return; }
+        log::debug!("Running {} shutdown hook(s)...", self.0.len());
+        let hooks = async {
+            for hook in self.0 {
+                hook().await;
+            }
+        };
+        if tokio::time::timeout(timeout, hooks).await.is_err() {
+            log::warn!("Shutdown hooks did not finish within {:?}", timeout);
+        }
+    }
 }
 
 impl CatalyzerRuntime {
     fn default_preinit() -> Result<CatalyzerRuntime> {
+        Self::default_builder()?.build()
+    }
+    /// Creates a builder for the runtime, with the same setup as the default runtime.
+    /// 
+    /// This sets up the built-in logger (if enabled), and a multi-threaded Tokio runtime.
+    /// It is useful for custom initialization functions that only want to tweak the defaults.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// # use catalyzer::internals::runtime::CatalyzerRuntime;
+    /// # use catalyzer::Result;
+    /// fn init() -> Result<CatalyzerRuntime> {
+    ///     CatalyzerRuntime::default_builder()?
+    ///         .on_shutdown(|| async { /* Flush buffers, close pools, ... */ })
+    ///         .build()
+    /// }
+    /// # init().unwrap();
+    /// ```
+    pub fn default_builder() -> Result<CatalyzerRuntimeBuilder> {
         #[cfg(feature = "builtin-logger")]
         {
             let log_level = std::env::var("CATALYZER_LOG_LEVEL").unwrap_or("info".to_string());
@@ -42,14 +111,20 @@ impl CatalyzerRuntime {
                     let id = ATOMIC_ID.fetch_add(1, Ordering::SeqCst);
                     format!("Catalyzer Runtime Worker #{id}")
                 })
-            )?
-            .build()
+            )
     }
     /// Creates a new builder for the runtime.
     #[inline]
     pub fn builder() -> CatalyzerRuntimeBuilder {
         CatalyzerRuntimeBuilder {
             tokio: None,
+            config: ShutdownConfig {
+                mercy_timeout: DEFAULT_MERCY_TIMEOUT,
+                shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+                hooks_timeout: DEFAULT_HOOKS_TIMEOUT,
+                force_exit: true,
+                hooks: ShutdownHooks(Vec::new()),
+            },
         }
     }
     /// Initializes the runtime with an optional custom initialization function.
@@ -65,6 +140,9 @@ impl CatalyzerRuntime {
     /// Runs the given future on the runtime.
     /// 
     /// This function will also install signal handlers for Ctrl+C and SIGTERM.
+    /// Once the future completes (or is abandoned after a second signal),
+    /// the registered shutdown hooks are run, and the runtime is torn down.
+    /// If the future returns an error, it is logged.
     ///
     /// # Example
     /// 
@@ -83,8 +161,15 @@ impl CatalyzerRuntime {
         Fut: Future<Output = Result>,
         F: FnOnce() -> Fut,
     {
+        let ShutdownConfig {
+            mercy_timeout,
+            shutdown_timeout,
+            hooks_timeout,
+            force_exit,
+            hooks,
+        } = self.config;
         let (sender, reciever) = tokio::sync::oneshot::channel::<()>();
-        let mercy_handlers = async {
+        let mercy_handlers = async move {
             tokio::select! {
                 _ = signals::ctrl_c() => {
                     log::info!("Received Ctrl+C, shutting down...");
@@ -93,31 +178,35 @@ impl CatalyzerRuntime {
                     log::info!("Received SIGTERM, shutting down...");
                 },
             }
+            // Dropping the sender would abandon the future, so keep it alive.
+            if !force_exit { return core::future::pending().await; }
             tokio::select! {
                 _ = signals::ctrl_c() => {},
                 _ = signals::term() => {},
             }
             log::warn!("Received second signal, please mercy...");
-            if let Err(_) = sender.send(()) {
+            if sender.send(()).is_err() {
                 log::error!("Failed to emit mercy signal, shutting down...");
                 std::process::exit(1);
             }
-            tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+            tokio::time::sleep(mercy_timeout).await;
             log::error!("Mercy timeout reached, shutting down...");
             std::process::exit(1);
         };
         self.tokio.spawn(mercy_handlers);
         self.tokio.block_on(async move {
             tokio::select! {
-                _ = f() => {
-                    log::debug!("Webserver shutdown successfully!");
+                res = f() => match res {
+                    Ok(()) => log::debug!("Webserver shutdown successfully!"),
+                    Err(e) => log::error!("{}", e),
                 },
                 _ = reciever => {
                     log::trace!("Received mercy signal, shutting down...");
                 },
             }
+            hooks.run(hooks_timeout).await;
         });
-        self.tokio.shutdown_timeout(tokio::time::Duration::from_secs(5));
+        self.tokio.shutdown_timeout(shutdown_timeout);
         log::info!("Shutdown successful!");
     }
 }
@@ -130,14 +219,15 @@ impl CatalyzerRuntimeBuilder {
     /// # Example
     /// 
     /// ```rust
-    /// # use catalyzer::internals::runtime::CatalyzerRuntimeBuilder;
+    /// # use catalyzer::internals::runtime::CatalyzerRuntime;
     /// # use catalyzer::Result;
     /// # fn main() -> Result {
     /// CatalyzerRuntime::builder()
     ///     .setup_tokio(|b| b.enable_all())?
     ///     .build()
-    /// # ;
+    /// # ?; Ok(())
     /// # }
+    /// ```
     pub fn setup_tokio<F>(mut self, f: F) -> Result<Self> where
         F: FnOnce(&mut TokioRuntimeBuilder) -> &mut TokioRuntimeBuilder,
     {
@@ -147,19 +237,69 @@ impl CatalyzerRuntimeBuilder {
             .map(|t| { self.tokio = Some(t); self})
             .map_auto()
     }
+    /// Sets how long to wait after a second Ctrl+C / SIGTERM before forcefully exiting.
+    /// 
+    /// Defaults to 10 seconds.
+    #[inline]
+    pub fn mercy_timeout(mut self, timeout: Duration) -> Self {
+        self.config.mercy_timeout = timeout;
+        self
+    }
+    /// Sets how long to wait for the remaining tasks when tearing down the runtime.
+    /// 
+    /// Defaults to 5 seconds.
+    #[inline]
+    pub fn shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.config.shutdown_timeout = timeout;
+        self
+    }
+    /// Sets whether a second Ctrl+C / SIGTERM forcefully exits the process.
+    /// 
+    /// When disabled, further signals are ignored, and the application
+    /// is always given the chance to shut down gracefully.
+    /// 
+    /// Enabled by default.
+    #[inline]
+    pub fn force_exit(mut self, enabled: bool) -> Self {
+        self.config.force_exit = enabled;
+        self
+    }
+    /// Registers an async hook to run on shutdown (e.g. to flush buffers, or close database pools).
+    /// 
+    /// Hooks run one after another, in the order they were registered,
+    /// after the application has stopped and before the runtime is torn down.
+    /// See [`shutdown_hooks_timeout`](CatalyzerRuntimeBuilder::shutdown_hooks_timeout)
+    /// for how long they are allowed to take.
+    pub fn on_shutdown<F, Fut>(mut self, hook: F) -> Self where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.config.hooks.0.push(Box::new(move || Box::pin(hook())));
+        self
+    }
+    /// Sets the deadline for all shutdown hooks combined.
+    /// 
+    /// Hooks still running when the deadline is reached are abandoned.
+    /// 
+    /// Defaults to 5 seconds.
+    #[inline]
+    pub fn shutdown_hooks_timeout(mut self, timeout: Duration) -> Self {
+        self.config.hooks_timeout = timeout;
+        self
+    }
     /// Builds the [`CatalyzerRuntime`](crate::internals::runtime::CatalyzerRuntime).
     /// 
     /// This function consumes the builder, and returns a runtime.
     pub fn build(self) -> Result<CatalyzerRuntime> {
         let tokio = self.tokio.ok_or(CatalyzerError::RuntimeInitializationError)?;
-        Ok(CatalyzerRuntime { tokio, })
+        Ok(CatalyzerRuntime { tokio, config: self.config })
     }
 }
 
 pub(crate) mod signals {
     use tokio::signal;
     pub(crate) async fn ctrl_c() {
-        if let Err(_) = signal::ctrl_c().await {
+        if signal::ctrl_c().await.is_err() {
             log::error!("Failed to install signal handler");
             std::process::exit(1);
         }
@@ -178,4 +318,40 @@ pub(crate) mod signals {
     pub(crate) async fn term() {
         core::future::pending::<()>().await;
     }
+    /// A stream of `SIGHUP` signals.
+    /// 
+    /// Signals received between two calls to [`recv`](Hangups::recv) are not lost.
+    #[cfg(unix)]
+    #[cfg_attr(not(feature = "tls"), allow(dead_code))]
+    pub(crate) struct Hangups(signal::unix::Signal);
+    #[cfg(unix)]
+    #[cfg_attr(not(feature = "tls"), allow(dead_code))]
+    impl Hangups {
+        pub(crate) fn new() -> Self {
+            match signal::unix::signal(signal::unix::SignalKind::hangup()) {
+                Ok(stream) => Self(stream),
+                Err(e) => {
+                    log::error!("Failed to install signal handler: {}", e);
+                    std::process::exit(1);
+                },
+            }
+        }
+        pub(crate) async fn recv(&mut self) {
+            self.0.recv().await;
+        }
+    }
+    /// A stream of `SIGHUP` signals, which never arrive on this platform.
+    #[cfg(not(unix))]
+    #[cfg_attr(not(feature = "tls"), allow(dead_code))]
+    pub(crate) struct Hangups;
+    #[cfg(not(unix))]
+    #[cfg_attr(not(feature = "tls"), allow(dead_code))]
+    impl Hangups {
+        pub(crate) fn new() -> Self {
+            Self
+        }
+        pub(crate) async fn recv(&mut self) {
+            core::future::pending::<()>().await;
+        }
+    }
 }
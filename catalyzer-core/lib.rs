@@ -19,3 +19,43 @@ pub mod internals;
 pub use error::{Result, CatalyzerError};
 #[doc(inline)]
 pub use app::App;
+#[doc(inline)]
+pub use app::shutdown::ShutdownHandle;
+#[doc(inline)]
+pub use app::health::Readiness;
+#[doc(inline)]
+pub use app::routing::Route;
+#[doc(inline)]
+pub use app::openapi::ApiSchema;
+#[doc(inline)]
+#[cfg(feature = "tls")]
+pub use app::tls::TlsConfig;
+#[doc(inline)]
+#[cfg(unix)]
+pub use app::unix::UnixSocketConfig;
+
+/// Registers a route handler, so that it can be mounted by `App::with_all_routes`.
+/// 
+/// This is used by the route macros, and does nothing without the `registry` feature.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "registry")]
+macro_rules! __register_handler {
+    ($handler:ident, $metadata:ident, $state:ty) => {
+        $crate::internals::crates::inventory::submit! {
+            $crate::internals::RegisteredHandler::new::<$metadata, $state>(
+                |method| $crate::internals::__registry_endpoint::<_, _, $state>(method, $handler)
+            )
+        }
+    };
+}
+
+/// Registers a route handler, so that it can be mounted by `App::with_all_routes`.
+/// 
+/// This is used by the route macros, and does nothing without the `registry` feature.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "registry"))]
+macro_rules! __register_handler {
+    ($($tt:tt)*) => {};
+}
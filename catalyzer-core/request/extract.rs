@@ -0,0 +1,205 @@
+use axum::extract::{FromRequest, FromRequestParts, MatchedPath};
+use axum::extract::rejection::PathRejection;
+use axum::http::request::Parts as RequestParts;
+use axum::response::{IntoResponse, Response};
+use axum::http::HeaderMap;
+use core::convert::Infallible;
+use crate::CatalyzerError;
+use std::future::Future;
+use super::RawRequest;
+use core::pin::Pin;
+
+type Extraction<'a, T, E> = Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'a>>;
+
+macro_rules! extractors {
+    ($(
+        $(#[$meta:meta])*
+        $kind:ident $name:ident$(<$t:ident>)?($inner:ty) => $axum:ty;
+    )*) => {$(
+        $(#[$meta])*
+        #[derive(Debug, Default, Clone)]
+        pub struct $name$(<$t>)?(pub $inner);
+
+        extractors!(@$kind $name$(<$t>)?($inner) => $axum);
+
+        impl$(<$t>)? core::ops::Deref for $name$(<$t>)? {
+            type Target = $inner;
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl$(<$t>)? core::ops::DerefMut for $name$(<$t>)? {
+            #[inline]
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+    )*};
+    (@parts $name:ident$(<$t:ident>)?($inner:ty) => $axum:ty) => {
+        impl<S, $($t)?> FromRequestParts<S> for $name$(<$t>)? where
+            $axum: FromRequestParts<S>,
+            CatalyzerError: From<<$axum as FromRequestParts<S>>::Rejection>,
+            S: Send + Sync,
+        {
+            type Rejection = CatalyzerError;
+            fn from_request_parts<'a: 'c, 'b: 'c, 'c>(
+                parts: &'a mut RequestParts,
+                state: &'b S,
+            ) -> Extraction<'c, Self, Self::Rejection> where Self: 'c {
+                Box::pin(async move {
+                    let extracted = <$axum>::from_request_parts(parts, state).await?;
+                    Ok(Self(extracted.0))
+                })
+            }
+        }
+    };
+    (@path $name:ident$(<$t:ident>)?($inner:ty) => $axum:ty) => {
+        impl<S, $($t)?> FromRequestParts<S> for $name$(<$t>)? where
+            $axum: FromRequestParts<S, Rejection = PathRejection>,
+            S: Send + Sync,
+        {
+            type Rejection = CatalyzerError;
+            fn from_request_parts<'a: 'c, 'b: 'c, 'c>(
+                parts: &'a mut RequestParts,
+                state: &'b S,
+            ) -> Extraction<'c, Self, Self::Rejection> where Self: 'c {
+                Box::pin(async move {
+                    let route = parts.extensions.get::<MatchedPath>().map(|m| m.as_str().to_string());
+                    match <$axum>::from_request_parts(parts, state).await {
+                        Ok(extracted) => Ok(Self(extracted.0)),
+                        Err(e) => {
+                            let field = crate::error::path_field(&e, route.as_deref());
+                            Err(crate::error::with_field(e.into(), field))
+                        },
+                    }
+                })
+            }
+        }
+    };
+    (@body $name:ident$(<$t:ident>)?($inner:ty) => $axum:ty) => {
+        impl<S, $($t)?> FromRequest<S> for $name$(<$t>)? where
+            $axum: FromRequest<S>,
+            CatalyzerError: From<<$axum as FromRequest<S>>::Rejection>,
+            S: Send + Sync,
+        {
+            type Rejection = CatalyzerError;
+            fn from_request<'a: 'b, 'b>(
+                req: RawRequest,
+                state: &'a S,
+            ) -> Extraction<'b, Self, Self::Rejection> where Self: 'b {
+                Box::pin(async move {
+                    let extracted = <$axum>::from_request(req, state).await?;
+                    Ok(Self(extracted.0))
+                })
+            }
+        }
+    };
+}
+
+extractors! {
+    /// An extractor that deserializes the query string of the request.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # use macros::*;
+    /// # use std::collections::HashMap;
+    /// #[get("/search")]
+    /// fn search(query: req::Query<HashMap<String, String>>) -> String {
+    ///     format!("Searching for {:?}", query.get("q"))
+    /// }
+    /// ```
+    parts Query<T>(T) => axum::extract::Query<T>;
+    /// An extractor that deserializes the path parameters of the request.
+    ///
+    /// The route macros bind path parameters to handler arguments on their own,
+    /// so this is only needed for handlers mounted some other way.
+    path Path<T>(T) => axum::extract::Path<T>;
+    /// An extractor that deserializes a JSON body, and a response that serializes one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # use macros::*;
+    /// # use std::collections::HashMap;
+    /// #[post("/echo")]
+    /// fn echo(body: req::Json<HashMap<String, u32>>) -> req::Json<HashMap<String, u32>> {
+    ///     body
+    /// }
+    /// ```
+    body Json<T>(T) => axum::Json<T>;
+    /// An extractor that deserializes a URL-encoded form body.
+    body Form<T>(T) => axum::Form<T>;
+    /// An extractor that collects the body of the request.
+    body Bytes(axum::body::Bytes) => BytesBody;
+}
+
+/// An extractor for the headers of the request.
+///
+/// # Example
+///
+/// ```rust
+/// # use catalyzer::*;
+/// # use macros::*;
+/// #[get("/agent")]
+/// fn agent(headers: req::Headers) -> String {
+///     format!("{:?}", headers.get("user-agent"))
+/// }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Headers(pub HeaderMap);
+
+impl<S: Send + Sync> FromRequestParts<S> for Headers {
+    type Rejection = Infallible;
+    fn from_request_parts<'a: 'c, 'b: 'c, 'c>(
+        parts: &'a mut RequestParts,
+        _: &'b S,
+    ) -> Extraction<'c, Self, Self::Rejection> where Self: 'c {
+        Box::pin(async move {
+            Ok(Self(parts.headers.clone()))
+        })
+    }
+}
+
+impl core::ops::Deref for Headers {
+    type Target = HeaderMap;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl core::ops::DerefMut for Headers {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> IntoResponse for Json<T> where
+    axum::Json<T>: IntoResponse
+{
+    #[inline]
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
+
+/// The body of a request, extracted by [`Bytes`].
+struct BytesBody(axum::body::Bytes);
+
+impl<S: Send + Sync> FromRequest<S> for BytesBody {
+    type Rejection = axum::extract::rejection::BytesRejection;
+    fn from_request<'a: 'b, 'b>(
+        req: RawRequest,
+        state: &'a S,
+    ) -> Extraction<'b, Self, Self::Rejection> where Self: 'b {
+        Box::pin(async move {
+            axum::body::Bytes::from_request(req, state).await.map(Self)
+        })
+    }
+}
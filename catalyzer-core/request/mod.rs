@@ -7,3 +7,5 @@ pub type RawRequest<T = Body> = ::axum::extract::Request<T>;
 
 mod state;
 pub use state::State;
+mod extract;
+pub use extract::{Query, Path, Json, Form, Headers, Bytes};
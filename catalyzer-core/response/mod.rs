@@ -7,6 +7,7 @@ use std::path::Path;
 use core::fmt;
 use crate::*;
 mod builtins;
+mod problem;
 
 /// A trait for types that can be converted into a [raw response].
 /// 
@@ -18,3 +19,5 @@ impl<R: Ir> IntoRawResponse for R {}
 pub type RawResponse<T = Body> = R<T>;
 
 pub use builtins::*;
+pub use problem::Problem;
+pub(crate) use problem::ProblemRenderer;
@@ -0,0 +1,114 @@
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use core::fmt;
+
+/// The content type of problem details.
+const PROBLEM_JSON: &str = "application/problem+json";
+
+/// Problem details of a rejected request, as defined by RFC 7807.
+///
+/// This is how Catalyzer's extractors (see [`req`](crate::req)) report rejected requests,
+/// rendered as `application/problem+json`. The rendering can be customized with
+/// [`App::render_rejections`](crate::App::render_rejections).
+///
+/// # Example
+///
+/// ```rust
+/// # use catalyzer::*;
+/// let app: App = App::new()
+///     .render_rejections(|problem: res::Problem| problem.detail().to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    status: StatusCode,
+    detail: String,
+    field: Option<String>,
+}
+
+impl Problem {
+    /// Creates new problem details.
+    pub fn new(status: StatusCode, detail: impl Into<String>) -> Self {
+        Self { status, detail: detail.into(), field: None }
+    }
+    /// Sets the part of the request that caused the problem.
+    pub fn with_field(mut self, field: impl Into<String>) -> Self {
+        self.field = Some(field.into());
+        self
+    }
+    /// The status of the response.
+    #[inline]
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+    /// A short summary of the problem (the reason phrase of the status).
+    #[inline]
+    pub fn title(&self) -> &'static str {
+        self.status.canonical_reason().unwrap_or("Error")
+    }
+    /// An explanation of the problem.
+    #[inline]
+    pub fn detail(&self) -> &str {
+        &self.detail
+    }
+    /// The part of the request that caused the problem, if known
+    /// (e.g. `user.name` for a JSON body, or `id` for a path parameter).
+    #[inline]
+    pub fn field(&self) -> Option<&str> {
+        self.field.as_deref()
+    }
+    /// Returns the problem details as a JSON object.
+    pub fn to_json(&self) -> Value {
+        let mut json = json!({
+            "type": "about:blank",
+            "title": self.title(),
+            "status": self.status.as_u16(),
+            "detail": self.detail,
+        });
+        if let Some(field) = &self.field {
+            json["field"] = json!(field);
+        }
+        json
+    }
+}
+
+impl IntoResponse for Problem {
+    fn into_response(self) -> Response {
+        let mut res = (self.status, self.to_json().to_string()).into_response();
+        res.headers_mut().insert(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+        // Kept around so that the rendering can be customized.
+        res.extensions_mut().insert(self);
+        res
+    }
+}
+
+/// A custom rendering of rejected requests.
+#[derive(Clone)]
+pub(crate) struct ProblemRenderer(Arc<dyn Fn(Problem) -> Response + Send + Sync>);
+
+impl ProblemRenderer {
+    /// Creates a renderer from a function.
+    pub(crate) fn new<F, R>(render: F) -> Self where
+        F: Fn(Problem) -> R + Send + Sync + 'static,
+        R: IntoResponse,
+    {
+        Self(Arc::new(move |problem| render(problem).into_response()))
+    }
+    /// Renders the response again, if it reports a problem.
+    /// 
+    /// The response keeps the status of the problem.
+    pub(crate) fn render(&self, mut res: Response) -> Response {
+        let Some(problem) = res.extensions_mut().remove::<Problem>() else { return res; };
+        let status = problem.status;
+        let mut res = (self.0)(problem);
+        *res.status_mut() = status;
+        res
+    }
+}
+
+impl fmt::Debug for ProblemRenderer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ProblemRenderer")
+    }
+}
@@ -0,0 +1,49 @@
+//! Automatic `HEAD` and `OPTIONS` responses, checked over HTTP.
+
+mod common;
+
+use catalyzer::*;
+use common::Server;
+use macros::*;
+
+#[get("/")]
+fn index() { "Hello, world!" }
+
+#[post("/")]
+fn submit() { "Submitted" }
+
+#[options("/custom")]
+fn custom_options() { "Custom" }
+
+#[tokio::test]
+async fn options_lists_allowed_methods() -> Result {
+    let server = Server::launch(App![index, submit].automatic_methods()).await?;
+    let res = server.request("OPTIONS", "/").await;
+    assert_eq!(res.status, 204);
+    assert_eq!(res.header("allow"), Some("GET, HEAD, POST, OPTIONS"));
+    assert_eq!(res.body, "");
+    server.stop().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn head_has_the_headers_but_no_body() -> Result {
+    let server = Server::launch(App![index].automatic_methods()).await?;
+    let res = server.request("HEAD", "/").await;
+    assert_eq!(res.status, 200);
+    assert_eq!(res.header("content-length"), Some("13"));
+    assert_eq!(res.body, "");
+    server.stop().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn handlers_of_automatic_methods_are_kept() -> Result {
+    let server = Server::launch(App![index, custom_options].automatic_methods()).await?;
+    assert_eq!(server.request("OPTIONS", "/custom").await.body, "Custom");
+    let res = server.request("DELETE", "/").await;
+    assert_eq!(res.status, 405);
+    assert_eq!(res.header("allow"), Some("GET, HEAD, OPTIONS"));
+    server.stop().await;
+    Ok(())
+}
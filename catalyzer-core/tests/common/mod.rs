@@ -0,0 +1,85 @@
+//! Helpers to send requests to applications served on a local port.
+
+#![allow(dead_code)]
+
+use catalyzer::*;
+use std::net::SocketAddr;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// An application served on a local port.
+pub struct Server {
+    addrs: Vec<SocketAddr>,
+    handle: ShutdownHandle,
+    task: tokio::task::JoinHandle<Result>,
+}
+
+impl Server {
+    /// Launches the application on a free local port.
+    pub async fn launch(app: App) -> Result<Self> {
+        Self::launch_with_signal(app, core::future::pending()).await
+    }
+    /// Launches the application on a free local port, shutting it down once the signal completes.
+    pub async fn launch_with_signal<F>(app: App, signal: F) -> Result<Self> where
+        F: core::future::Future<Output = ()> + Send + 'static,
+    {
+        let app = app.bind("127.0.0.1:0")?
+            .with_graceful_shutdown(signal)
+            .launch().await?;
+        let addrs = app.local_addrs().to_vec();
+        let handle = app.shutdown_handle();
+        let task = tokio::spawn(async move { app.await });
+        Ok(Self { addrs, handle, task })
+    }
+    /// Returns the addresses the application listens on, the local port first.
+    pub fn local_addrs(&self) -> &[SocketAddr] {
+        &self.addrs
+    }
+    /// Sends a request without a body, and reads the whole response.
+    pub async fn request(&self, method: &str, path: &str) -> Response {
+        self.request_with_headers(method, path, &[("Host", "localhost")]).await
+    }
+    /// Sends a request without a body and with the given headers, and reads the whole response.
+    pub async fn request_with_headers(&self, method: &str, path: &str, headers: &[(&str, &str)]) -> Response {
+        let mut stream = TcpStream::connect(self.addrs[0]).await.unwrap();
+        let headers = headers.iter().map(|(name, value)| format!("{name}: {value}\r\n")).collect::<String>();
+        let request = format!("{method} {path} HTTP/1.1\r\n{headers}Connection: close\r\n\r\n");
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut raw = Vec::new();
+        stream.read_to_end(&mut raw).await.unwrap();
+        Response::parse(&String::from_utf8(raw).unwrap())
+    }
+    /// Shuts the application down, and waits for it to stop.
+    pub async fn stop(self) {
+        self.handle.shutdown();
+        self.task.await.unwrap().unwrap();
+    }
+}
+
+/// A response, as read off the wire.
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Response {
+    fn parse(raw: &str) -> Self {
+        let (head, body) = raw.split_once("\r\n\r\n").unwrap();
+        let mut lines = head.split("\r\n");
+        let status = lines.next().unwrap().split(' ').nth(1).unwrap().parse().unwrap();
+        let headers = lines
+            .map(|line| line.split_once(':').unwrap())
+            .map(|(name, value)| (name.to_lowercase(), value.trim().to_string()))
+            .collect();
+        Self { status, headers, body: body.to_string() }
+    }
+    /// Returns the only value of a header, if present.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        let mut values = self.headers.iter().filter(|(n, _)| n == name);
+        let value = values.next().map(|(_, v)| v.as_str());
+        assert!(values.next().is_none(), "duplicate `{name}` header");
+        value
+    }
+}
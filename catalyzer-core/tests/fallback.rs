@@ -0,0 +1,51 @@
+//! Fallbacks and the debug 404 page, checked over HTTP.
+
+mod common;
+
+use catalyzer::*;
+use common::Server;
+use macros::*;
+
+#[get("/")]
+fn index() { "Index" }
+
+#[get("/users")]
+fn users() { "Users" }
+
+#[fallback]
+fn not_found() { "Nothing to see here" }
+
+#[tokio::test]
+async fn fallback_answers_unmatched_paths() -> Result {
+    let server = Server::launch(App![index, not_found]).await?;
+    let res = server.request("GET", "/missing").await;
+    assert_eq!(res.status, 200);
+    assert_eq!(res.body, "Nothing to see here");
+    assert_eq!(server.request("GET", "/").await.body, "Index");
+    server.stop().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn nested_fallback_answers_under_its_prefix() -> Result {
+    let api: App = App![users, not_found];
+    let server = Server::launch(App![index].nest("/api", api)?).await?;
+    assert_eq!(server.request("GET", "/api/users").await.body, "Users");
+    assert_eq!(server.request("GET", "/api/missing").await.body, "Nothing to see here");
+    assert_eq!(server.request("GET", "/missing").await.status, 404);
+    server.stop().await;
+    Ok(())
+}
+
+#[cfg(debug_assertions)]
+#[tokio::test]
+async fn not_found_page_lists_routes() -> Result {
+    let server = Server::launch(App![index].nest("/api", App![users])?).await?;
+    let res = server.request("GET", "/missing").await;
+    assert_eq!(res.status, 404);
+    assert!(res.body.contains("No route matches GET /missing"), "{}", res.body);
+    assert!(res.body.contains("/api/users"), "{}", res.body);
+    assert!(res.body.contains("index"), "{}", res.body);
+    server.stop().await;
+    Ok(())
+}
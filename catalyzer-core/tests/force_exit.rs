@@ -0,0 +1,32 @@
+//! Graceful shutdown on `SIGTERM` when forceful exits are disabled.
+//! 
+//! This sends a real signal to the test process, so it lives in its own test binary.
+
+#![cfg(unix)]
+
+use catalyzer::internals::runtime::CatalyzerRuntime;
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use catalyzer::*;
+
+static FINISHED: AtomicBool = AtomicBool::new(false);
+
+#[test]
+fn sigterm_lets_the_future_finish() -> Result {
+    let runtime = CatalyzerRuntime::builder()
+        .setup_tokio(|b| b.enable_all())?
+        .force_exit(false)
+        .build()?;
+    runtime.run(|| async {
+        // Give the runtime time to install its signal handlers.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        // SAFETY: sending a signal to our own process.
+        unsafe { libc::kill(libc::getpid(), libc::SIGTERM) };
+        // Keep draining for a while after the signal.
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        FINISHED.store(true, Ordering::SeqCst);
+        Ok(())
+    });
+    assert!(FINISHED.load(Ordering::SeqCst));
+    Ok(())
+}
@@ -0,0 +1,48 @@
+//! Health endpoints, checked over HTTP.
+
+mod common;
+
+use catalyzer::*;
+use common::Server;
+use std::time::Duration;
+
+#[tokio::test]
+async fn health_endpoints_answer_while_serving() -> Result {
+    let server = Server::launch(App::new().health_checks()).await?;
+    let live = server.request("GET", "/health/live").await;
+    assert_eq!(live.status, 200);
+    let ready = server.request("GET", "/health/ready").await;
+    assert_eq!(ready.status, 200);
+    server.stop().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn ready_answers_503_while_shutting_down() -> Result {
+    let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+    let app = App::new()
+        .health_checks()
+        .pre_stop_delay(Duration::from_secs(5));
+    let server = Server::launch_with_signal(app, async { let _ = rx.await; }).await?;
+    assert_eq!(server.request("GET", "/health/ready").await.status, 200);
+    tx.send(()).unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    // Still serving during the pre-stop delay, but no longer ready.
+    assert_eq!(server.request("GET", "/health/ready").await.status, 503);
+    assert_eq!(server.request("GET", "/health/live").await.status, 200);
+    server.stop().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn readiness_handle_controls_the_ready_endpoint() -> Result {
+    let app = App::new().health_checks();
+    let readiness = app.readiness();
+    let server = Server::launch(app).await?;
+    readiness.set_ready(false);
+    assert_eq!(server.request("GET", "/health/ready").await.status, 503);
+    readiness.set_ready(true);
+    assert_eq!(server.request("GET", "/health/ready").await.status, 200);
+    server.stop().await;
+    Ok(())
+}
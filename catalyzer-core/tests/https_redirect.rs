@@ -0,0 +1,84 @@
+//! Redirects from plain HTTP to HTTPS, checked over HTTP.
+
+#![cfg(feature = "tls")]
+
+mod common;
+
+use catalyzer::*;
+use common::Server;
+use macros::*;
+
+#[get("/")]
+fn index() { "Index" }
+
+fn tls() -> TlsConfig {
+    TlsConfig::from_pem(
+        include_bytes!("fixtures/cert.pem").as_slice(),
+        include_bytes!("fixtures/key.pem").as_slice(),
+    )
+}
+
+async fn launch(https: &str) -> Result<Server> {
+    Server::launch(App![index].bind_https(https, tls())?.redirect_to_https()).await
+}
+
+#[tokio::test]
+async fn redirect_keeps_the_host_path_and_query() -> Result {
+    let server = launch("127.0.0.1:0").await?;
+    let port = server.local_addrs()[1].port();
+    let res = server.request_with_headers("GET", "/users?page=2", &[("Host", "example.com:8080")]).await;
+    assert_eq!(res.status, 308);
+    assert_eq!(res.header("location"), Some(format!("https://example.com:{port}/users?page=2").as_str()));
+    server.stop().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn redirect_keeps_ipv6_hosts_in_brackets() -> Result {
+    let server = launch("127.0.0.1:0").await?;
+    let port = server.local_addrs()[1].port();
+    let res = server.request_with_headers("GET", "/", &[("Host", "[::1]:8080")]).await;
+    assert_eq!(res.status, 308);
+    assert_eq!(res.header("location"), Some(format!("https://[::1]:{port}/").as_str()));
+    server.stop().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn redirect_falls_back_to_the_https_address() -> Result {
+    let server = launch("127.0.0.1:0").await?;
+    let port = server.local_addrs()[1].port();
+    let res = server.request_with_headers("GET", "/", &[]).await;
+    assert_eq!(res.status, 308);
+    assert_eq!(res.header("location"), Some(format!("https://127.0.0.1:{port}/").as_str()));
+    server.stop().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn redirect_falls_back_to_an_ipv6_https_address_in_brackets() -> Result {
+    let Ok(server) = launch("[::1]:0").await else {
+        eprintln!("Skipped, IPv6 is not available");
+        return Ok(());
+    };
+    let port = server.local_addrs()[1].port();
+    let res = server.request_with_headers("GET", "/", &[]).await;
+    assert_eq!(res.status, 308);
+    assert_eq!(res.header("location"), Some(format!("https://[::1]:{port}/").as_str()));
+    server.stop().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn redirect_drops_the_default_https_port() -> Result {
+    // Binding port 443 needs privileges the test may not have.
+    let Ok(server) = launch("127.0.0.1:443").await else {
+        eprintln!("Skipped, port 443 is not available");
+        return Ok(());
+    };
+    let res = server.request_with_headers("GET", "/users", &[("Host", "example.com")]).await;
+    assert_eq!(res.status, 308);
+    assert_eq!(res.header("location"), Some("https://example.com/users"));
+    server.stop().await;
+    Ok(())
+}
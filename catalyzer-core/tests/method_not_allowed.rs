@@ -0,0 +1,72 @@
+//! `405 Method Not Allowed` responses and their `Allow` header, checked over HTTP.
+
+mod common;
+
+use catalyzer::*;
+use common::Server;
+use macros::*;
+
+#[get("/items")]
+fn list() { "Items" }
+
+#[post("/items")]
+fn create() { "Created" }
+
+#[delete("/items")]
+fn remove() { "Removed" }
+
+#[method("PROPFIND", "/items")]
+fn properties() { "Properties" }
+
+#[tokio::test]
+async fn lists_allowed_methods() -> Result {
+    let server = Server::launch(App![list, create]).await?;
+    let res = server.request("PUT", "/items").await;
+    assert_eq!(res.status, 405);
+    assert_eq!(res.header("allow"), Some("GET, HEAD, POST"));
+    assert_eq!(res.body, "");
+    server.stop().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn custom_handler_keeps_status_and_allow() -> Result {
+    let app = App![list].method_not_allowed(|| async { "Try another method" });
+    let server = Server::launch(app).await?;
+    let res = server.request("DELETE", "/items").await;
+    assert_eq!(res.status, 405);
+    assert_eq!(res.header("allow"), Some("GET, HEAD"));
+    assert_eq!(res.body, "Try another method");
+    server.stop().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn custom_methods_answer_with_the_allow_header_of_their_path() -> Result {
+    let server = Server::launch(App![list, properties]).await?;
+    let res = server.request("PROPFIND", "/items").await;
+    assert_eq!(res.status, 200);
+    assert_eq!(res.header("allow"), Some("GET, HEAD, PROPFIND"));
+    assert_eq!(res.body, "Properties");
+    server.stop().await;
+    Ok(())
+}
+
+#[derive(Clone)]
+struct AppState;
+
+#[tokio::test]
+async fn routes_added_after_the_state_are_allowed() -> Result {
+    let app: App<AppState> = App![list];
+    let app: App = app.set_state(AppState)
+        .route::<_, remove_metadata, _>(remove)?
+        .route::<_, properties_metadata, _>(properties)?;
+    let server = Server::launch(app).await?;
+    assert_eq!(server.request("DELETE", "/items").await.body, "Removed");
+    assert_eq!(server.request("PROPFIND", "/items").await.body, "Properties");
+    let res = server.request("PATCH", "/items").await;
+    assert_eq!(res.status, 405);
+    assert_eq!(res.header("allow"), Some("GET, HEAD, DELETE, PROPFIND"));
+    server.stop().await;
+    Ok(())
+}
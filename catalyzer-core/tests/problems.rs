@@ -0,0 +1,40 @@
+//! Rejected requests, checked over HTTP.
+
+mod common;
+
+use catalyzer::*;
+use common::Server;
+use macros::*;
+
+#[get("/users/{id}")]
+fn user(id: u32) -> String { format!("User {id}") }
+
+#[tokio::test]
+async fn rejections_are_answered_as_problem_details() -> Result {
+    let server = Server::launch(App![user]).await?;
+    let res = server.request("GET", "/users/abc").await;
+    assert_eq!(res.status, 400);
+    assert_eq!(res.header("content-type"), Some("application/problem+json"));
+    let body: serde_json::Value = serde_json::from_str(&res.body).unwrap();
+    assert_eq!(body["type"], "about:blank");
+    assert_eq!(body["title"], "Bad Request");
+    assert_eq!(body["status"], 400);
+    assert_eq!(body["field"], "id");
+    assert!(body["detail"].is_string());
+    assert_eq!(server.request("GET", "/users/7").await.body, "User 7");
+    server.stop().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn rendered_rejections_keep_the_status() -> Result {
+    let app = App![user]
+        .render_rejections(|problem: res::Problem| format!("{}: {}", problem.title(), problem.field().unwrap_or("?")));
+    let server = Server::launch(app).await?;
+    let res = server.request("GET", "/users/abc").await;
+    assert_eq!(res.status, 400);
+    assert_eq!(res.header("content-type"), Some("text/plain; charset=utf-8"));
+    assert_eq!(res.body, "Bad Request: id");
+    server.stop().await;
+    Ok(())
+}
@@ -1,17 +1,35 @@
 use syn::parse::*;
 use crate::*;
 
-type IdentList = syn::punctuated::Punctuated<syn::Ident, syn::Token![,]>;
+type PathList = syn::punctuated::Punctuated<syn::Path, syn::Token![,]>;
 
 pub(crate) fn app(input: T) -> T {
-    let items = match IdentList::parse_terminated.parse2(input) {
+    if syn::parse2::<syn::Token![*]>(input.clone()).is_ok() {
+        return quote::quote!(::catalyzer::App::new().with_all_routes()?);
+    }
+    let items = match PathList::parse_terminated.parse2(input) {
         Err(e) => return e.to_compile_error(),
         Ok(i) => i,
     };
     let mut res = quote::quote!(::catalyzer::App::new());
     for item in items {
-        let item_metadata = syn::Ident::new(&format!("{}_metadata", item), item.span());
+        let item_metadata = match metadata_path(&item) {
+            Err(e) => return e.to_compile_error(),
+            Ok(m) => m,
+        };
         res = quote::quote!(#res.route::<_, #item_metadata, _>(#item)?);
     };
     res
 }
+
+/// Resolves the metadata type of a handler, which lives in the same module.
+fn metadata_path(item: &syn::Path) -> syn::Result<syn::Path> {
+    let mut metadata = item.clone();
+    let last = metadata.segments.last_mut()
+        .ok_or_else(|| syn::Error::new_spanned(item, "expected a handler"))?;
+    if !last.arguments.is_none() {
+        return Err(syn::Error::new_spanned(&last.arguments, "handlers can't have generic arguments"));
+    }
+    last.ident = syn::Ident::new(&format!("{}_metadata", last.ident), last.ident.span());
+    Ok(metadata)
+}
@@ -8,17 +8,22 @@ use proc_macro::TokenStream;
 
 mod main_func;
 mod routes;
+mod path;
 mod app;
 
 /// Marks a function as the main entry point for the application.
 /// 
+/// The function runs on the Catalyzer runtime (see `CatalyzerRuntime::run`).
+/// If it returns an error, the error is logged.
+/// 
 /// # Example
 /// 
-/// ```rust
+/// ```rust,no_run
 /// # use catalyzer::*;
 /// #[main]
 /// fn main() {
 ///     // Your code here (can be both sync and async)
+///     App![].bind("0.0.0.0:3000")?.launch()
 /// }
 /// ```
 #[proc_macro_attribute]
@@ -32,7 +37,7 @@ pub fn main(cfg: TokenStream, input: TokenStream) -> TokenStream {
 /// 
 /// # Example
 /// 
-/// ```rust
+/// ```rust,no_run
 /// # use catalyzer::*;
 /// #[main]
 /// fn main() {
@@ -46,12 +51,93 @@ pub fn main(cfg: TokenStream, input: TokenStream) -> TokenStream {
 ///     "Hello, world!"
 /// }
 /// ```
+/// 
+/// Handlers from other modules can be referenced by their path:
+/// 
+/// ```rust,no_run
+/// # use catalyzer::*;
+/// mod api {
+///     pub mod users {
+///         use catalyzer::*;
+///         #[get("/users")]
+///         pub fn list() { "Users" }
+///     }
+/// }
+/// 
+/// #[main]
+/// fn main() {
+///     App![api::users::list]
+///         .bind("0.0.0.0:3000")?
+///         .launch()
+/// }
+/// ```
+/// 
+/// With the `registry` feature, `App![*]` mounts every handler in the program
+/// (see `App::with_all_routes`).
 #[proc_macro]
 #[allow(non_snake_case)]
 pub fn App(input: TokenStream) -> TokenStream {
     app::app(input.into()).into()
 }
 
+/// A route handler for several methods at once.
+/// 
+/// # Example
+/// 
+/// ```rust,no_run
+/// # use catalyzer::*;
+/// #[route("/items/{id}", methods = [PUT, PATCH])]
+/// fn update(id: u64) -> String {
+///     format!("Updated item {id}")
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn route(cfg: TokenStream, input: TokenStream) -> TokenStream {
+    routes::route(cfg.into(), input.into()).into()
+}
+
+/// A route handler for any method, including extension methods (e.g. WebDAV's `PROPFIND`).
+/// 
+/// # Example
+/// 
+/// ```rust,no_run
+/// # use catalyzer::*;
+/// #[method("PROPFIND", "/files/{*path}")]
+/// fn properties(path: String) -> String {
+///     format!("Properties of {path}")
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn method(cfg: TokenStream, input: TokenStream) -> TokenStream {
+    routes::method(cfg.into(), input.into()).into()
+}
+
+/// A handler for requests no route matches.
+/// 
+/// It is mounted like any other handler.
+/// 
+/// # Example
+/// 
+/// ```rust,no_run
+/// # use catalyzer::*;
+/// #[main]
+/// fn main() {
+///     App![index, not_found]
+///         .bind("0.0.0.0:3000")?
+///         .launch()
+/// }
+/// 
+/// #[get("/")]
+/// fn index() { "Hello, world!" }
+/// 
+/// #[fallback]
+/// fn not_found() { "Nothing to see here" }
+/// ```
+#[proc_macro_attribute]
+pub fn fallback(cfg: TokenStream, input: TokenStream) -> TokenStream {
+    routes::fallback(cfg.into(), input.into()).into()
+}
+
 macro_rules! routes {
     ($(
         $(#[$attr:meta])*
@@ -67,6 +153,85 @@ macro_rules! routes {
 
 routes!(
     /// A route handler for the `GET` method.
+    /// 
+    /// The path can contain parameters (`{name}`, or `{*name}` for the rest of the path),
+    /// which are bound to the handler arguments of the same name.
+    /// Parameters in the router syntax (`:name` and `*name`) are bound the same way.
+    /// 
+    /// ```rust,no_run
+    /// # use catalyzer::*;
+    /// #[get("/users/{id}/posts/{post_id}")]
+    /// fn post(id: u64, post_id: u64) -> String {
+    ///     format!("Post {post_id} of user {id}")
+    /// }
+    /// ```
+    /// 
+    /// Handlers with a `Path` extractor are left as they are, and extract the parameters themselves:
+    /// 
+    /// ```rust
+    /// # use catalyzer::*;
+    /// # use catalyzer::internals::crates::axum::extract::Path;
+    /// #[get("/legacy/:id")]
+    /// fn legacy(Path(id): Path<u32>) -> String {
+    ///     format!("Legacy item {id}")
+    /// }
+    /// 
+    /// #[get("/users/{id}/posts/{post_id}")]
+    /// fn post(req::Path((id, post_id)): req::Path<(u64, u64)>) -> String {
+    ///     format!("Post {post_id} of user {id}")
+    /// }
+    /// ```
+    /// 
+    /// Invalid paths are reported at compile time.
+    /// Paths must start with `/`:
+    /// 
+    /// ```rust,compile_fail
+    /// # use catalyzer::*;
+    /// #[get("users")]
+    /// fn users() { "" }
+    /// ```
+    /// Braces must be balanced:
+    /// 
+    /// ```rust,compile_fail
+    /// # use catalyzer::*;
+    /// #[get("/users/{id")]
+    /// fn user(id: u32) { "" }
+    /// ```
+    /// Wildcards must be the last segment:
+    /// 
+    /// ```rust,compile_fail
+    /// # use catalyzer::*;
+    /// #[get("/files/{*path}/raw")]
+    /// fn raw(path: String) { "" }
+    /// ```
+    /// Parameter names must be unique:
+    /// 
+    /// ```rust,compile_fail
+    /// # use catalyzer::*;
+    /// #[get("/users/{id}/friends/{id}")]
+    /// fn friend(id: u32) { "" }
+    /// ```
+    /// Parameter names must be identifiers, also in the router syntax:
+    /// 
+    /// ```rust,compile_fail
+    /// # use catalyzer::*;
+    /// #[get("/files/*")]
+    /// fn files() { "" }
+    /// ```
+    /// Every parameter must be bound to an argument:
+    /// 
+    /// ```rust,compile_fail
+    /// # use catalyzer::*;
+    /// #[get("/users/{id}")]
+    /// fn user() { "" }
+    /// ```
+    /// And every scalar argument to a parameter:
+    /// 
+    /// ```rust,compile_fail
+    /// # use catalyzer::*;
+    /// #[get("/users")]
+    /// fn user(id: u32) { "" }
+    /// ```
     @get
     /// A route handler for the `POST` method.
     @post
@@ -82,4 +247,6 @@ routes!(
     @options
     /// A route handler for the `TRACE` method.
     @trace
+    /// A route handler for the `CONNECT` method.
+    @connect
 );
@@ -0,0 +1,130 @@
+//! Parsing and validation of route paths.
+
+use core::ops::Range;
+
+/// A parsed route path.
+pub(crate) struct RoutePath {
+    /// The path, in the syntax understood by the router.
+    pub(crate) route: String,
+    /// The parameters of the path, in order of appearance.
+    pub(crate) params: Vec<Param>,
+}
+
+/// A path parameter (`{id}`, or `{*rest}` for a wildcard; `:id` and `*rest` in the router syntax).
+pub(crate) struct Param {
+    pub(crate) name: String,
+}
+
+impl RoutePath {
+    /// Parses a route path like `/users/{id}/files/{*path}`.
+    ///
+    /// All problems found in the path are reported at once.
+    pub(crate) fn parse(lit: &syn::LitStr) -> syn::Result<Self> {
+        let path = lit.value();
+        let mut errors = Errors { lit, list: Vec::new() };
+        if !path.starts_with('/') {
+            errors.push(0..0, "route paths must start with `/`".to_string());
+        }
+        let mut route = String::with_capacity(path.len());
+        let mut params = Vec::new();
+        let mut names = Vec::<&str>::new();
+        let segments = path.split('/').count();
+        let mut start = 0;
+        for (i, segment) in path.split('/').enumerate() {
+            let range = start..start + segment.len();
+            start = range.end + 1;
+            if i > 0 { route.push('/'); }
+            let last = i + 1 == segments;
+            if segment.contains(['{', '}']) {
+                let opens = segment.matches('{').count();
+                let closes = segment.matches('}').count();
+                let inner = segment.strip_prefix('{').and_then(|s| s.strip_suffix('}'));
+                let inner = match inner {
+                    _ if opens != closes => {
+                        errors.push(range, format!("unbalanced braces in `{segment}`"));
+                        continue;
+                    },
+                    Some(inner) if opens == 1 => inner,
+                    _ => {
+                        errors.push(range, format!(
+                            "invalid segment `{segment}`, a parameter must take up the whole segment"
+                        ));
+                        continue;
+                    },
+                };
+                let (name, wildcard) = match inner.strip_prefix('*') {
+                    Some(name) => (name, true),
+                    None => (inner, false),
+                };
+                if syn::parse_str::<syn::Ident>(name).is_err() {
+                    errors.push(range, format!(
+                        "invalid path parameter `{segment}`, expected an identifier"
+                    ));
+                    continue;
+                }
+                if wildcard && !last {
+                    errors.push(range.clone(), format!(
+                        "wildcard `{segment}` must be the last segment of the path"
+                    ));
+                }
+                if names.contains(&name) {
+                    errors.push(range.clone(), format!("duplicate path parameter `{name}`"));
+                }
+                names.push(name);
+                route.push(if wildcard { '*' } else { ':' });
+                route.push_str(name);
+                params.push(Param { name: name.to_string() });
+                continue;
+            }
+            // Parameters in the router syntax are passed through, and bound like the others.
+            if let Some(name) = segment.strip_prefix(':').or(segment.strip_prefix('*')) {
+                if syn::parse_str::<syn::Ident>(name).is_err() {
+                    errors.push(range, format!(
+                        "invalid path parameter `{segment}`, expected an identifier"
+                    ));
+                    continue;
+                }
+                if segment.starts_with('*') && !last {
+                    errors.push(range.clone(), format!(
+                        "wildcard `{segment}` must be the last segment of the path"
+                    ));
+                }
+                if names.contains(&name) {
+                    errors.push(range.clone(), format!("duplicate path parameter `{name}`"));
+                }
+                names.push(name);
+                params.push(Param { name: name.to_string() });
+            }
+            route.push_str(segment);
+        }
+        errors.finish()?;
+        Ok(Self { route, params })
+    }
+}
+
+/// Errors found in a path literal.
+struct Errors<'a> {
+    lit: &'a syn::LitStr,
+    list: Vec<syn::Error>,
+}
+
+impl Errors<'_> {
+    /// Adds an error, pointing at the given range of the path where possible.
+    fn push(&mut self, range: Range<usize>, message: String) {
+        let token = self.lit.token();
+        // Only plain literals without escapes map one-to-one onto the source.
+        let plain = token.to_string() == format!("\"{}\"", self.lit.value());
+        let span = match plain {
+            true => token.subspan(range.start + 1..range.end + 1),
+            false => None,
+        };
+        self.list.push(syn::Error::new(span.unwrap_or(self.lit.span()), message));
+    }
+    /// Combines all errors into one, if there are any.
+    fn finish(self) -> syn::Result<()> {
+        match self.list.into_iter().reduce(|mut a, b| { a.combine(b); a }) {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
@@ -10,57 +10,442 @@ impl quote::ToTokens for Watermark {
     }
 }
 
+type Inputs = syn::punctuated::Punctuated<syn::FnArg, syn::Token![,]>;
+
+/// Replaces the handler arguments named after path parameters with a single `Path` extractor.
+/// 
+/// Handlers that already extract the path themselves (with a `Path` extractor) are left untouched.
+fn bind_params(route: &path::RoutePath, lit: &syn::LitStr, inputs: &mut Inputs) -> syn::Result<()> {
+    if inputs.iter().any(is_path_extractor) { return Ok(()); }
+    let mut errors = Vec::new();
+    let mut bound = Vec::new();
+    for param in &route.params {
+        let found = inputs.iter().position(|arg| matches!(
+            arg_ident(arg), Some(ident) if ident == &param.name
+        ));
+        match found {
+            Some(i) => bound.push(i),
+            None => errors.push(syn::Error::new(lit.span(), format!(
+                "path parameter `{}` has no matching handler argument", param.name
+            ))),
+        }
+    }
+    for (i, arg) in inputs.iter().enumerate() {
+        let syn::FnArg::Typed(typed) = arg else { continue; };
+        if bound.contains(&i) || !is_scalar(&typed.ty) { continue; }
+        if let Some(ident) = arg_ident(arg) {
+            errors.push(syn::Error::new(ident.span(), format!(
+                "argument `{}` does not match any parameter of \"{}\"", ident, lit.value()
+            )));
+        }
+    }
+    if let Some(e) = errors.into_iter().reduce(|mut a, b| { a.combine(b); a }) {
+        return Err(e);
+    }
+    if bound.is_empty() { return Ok(()); }
+    let args = inputs.clone().into_iter().collect::<Vec<_>>();
+    let (mut pats, mut tys) = (Vec::new(), Vec::new());
+    for &i in &bound {
+        if let syn::FnArg::Typed(typed) = &args[i] {
+            pats.push(typed.pat.clone());
+            tys.push(typed.ty.clone());
+        }
+    }
+    let extractor: syn::FnArg = syn::parse_quote! {
+        ::catalyzer::req::Path((#(#pats,)*)): ::catalyzer::req::Path<(#(#tys,)*)>
+    };
+    *inputs = core::iter::once(extractor)
+        .chain(args.into_iter().enumerate().filter(|(i, _)| !bound.contains(i)).map(|(_, a)| a))
+        .collect();
+    Ok(())
+}
+
+/// Generates the OpenAPI metadata of a handler, from its doc comments,
+/// path parameters, arguments and return type (before the parameters are bound).
+fn api_metadata(func: &syn::ItemFn, route: Option<&path::RoutePath>) -> T {
+    let mut lines = Vec::new();
+    for attr in &func.attrs {
+        let syn::Meta::NameValue(meta) = &attr.meta else { continue; };
+        if !meta.path.is_ident("doc") { continue; }
+        if let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(doc), .. }) = &meta.value {
+            let doc = doc.value();
+            lines.push(doc.strip_prefix(' ').unwrap_or(&doc).trim_end().to_string());
+        }
+    }
+    let doc = lines.join("\n");
+    let (summary, description) = match doc.trim().split_once("\n\n") {
+        Some((summary, description)) => (summary.trim(), description.trim()),
+        None => (doc.trim(), ""),
+    };
+    let summary = summary.split_whitespace().collect::<Vec<_>>().join(" ");
+    let optional = |s: &str| match s.is_empty() {
+        true => quote::quote!(::core::option::Option::None),
+        false => quote::quote!(::core::option::Option::Some(#s)),
+    };
+    let (summary, description) = (optional(&summary), optional(description));
+    let docs = quote::quote! {
+        const SUMMARY: ::core::option::Option<&'static str> = #summary;
+        const DESCRIPTION: ::core::option::Option<&'static str> = #description;
+    };
+    // Types can't refer to the generics of the handler.
+    if !func.sig.generics.params.is_empty() {
+        return docs;
+    }
+    let mut params = Vec::new();
+    for param in route.map_or(&[][..], |r| &r.params[..]) {
+        let ty = func.sig.inputs.iter().find_map(|arg| match arg {
+            syn::FnArg::Typed(typed) if arg_ident(arg).is_some_and(|i| i == &param.name) => Some(&typed.ty),
+            _ => None,
+        });
+        let (Some(ty), name) = (ty, &param.name) else { continue; };
+        let schema = schema_of(ty);
+        params.push(quote::quote!(::catalyzer::internals::ApiParam {
+            name: #name,
+            location: ::catalyzer::internals::ApiParamLocation::Path,
+            schema: #schema,
+        }));
+    }
+    let mut request_body = quote::quote!(::core::option::Option::None);
+    for arg in &func.sig.inputs {
+        let syn::FnArg::Typed(typed) = arg else { continue; };
+        if route.is_some_and(|r| arg_ident(arg).is_some_and(|i| r.params.iter().any(|p| i == &p.name))) {
+            continue;
+        }
+        if let Some(query) = generic_arg(&typed.ty, "Query") {
+            let name = arg_ident(arg).map_or("query".to_string(), |i| i.to_string());
+            let schema = schema_of(query);
+            params.push(quote::quote!(::catalyzer::internals::ApiParam {
+                name: #name,
+                location: ::catalyzer::internals::ApiParamLocation::Query,
+                schema: #schema,
+            }));
+            continue;
+        }
+        if let Some(body) = request_body_of(&typed.ty) {
+            request_body = quote::quote!(::core::option::Option::Some(#body));
+        }
+    }
+    let response_body = match &func.sig.output {
+        syn::ReturnType::Type(_, ty) => match response_body_of(ty) {
+            Some(body) => quote::quote!(::core::option::Option::Some(#body)),
+            None => quote::quote!(::core::option::Option::None),
+        },
+        syn::ReturnType::Default => quote::quote!(::core::option::Option::None),
+    };
+    quote::quote! {
+        #docs
+        const PARAMS: &'static [::catalyzer::internals::ApiParam] = &[#(#params),*];
+        const REQUEST_BODY: ::core::option::Option<::catalyzer::internals::ApiBody> = #request_body;
+        const RESPONSE_BODY: ::core::option::Option<::catalyzer::internals::ApiBody> = #response_body;
+    }
+}
+
+/// Returns a function describing the schema of the type.
+fn schema_of(ty: &syn::Type) -> T {
+    quote::quote!(|| {
+        use ::catalyzer::internals::{__KnownSchema as _, __UnknownSchema as _};
+        (&&::catalyzer::internals::SchemaProbe::<#ty>::NEW).schema()
+    })
+}
+
+/// Returns a body with the given content type and schema.
+fn body(content_type: &str, schema: T) -> T {
+    quote::quote!(::catalyzer::internals::ApiBody { content_type: #content_type, schema: #schema })
+}
+
+/// Returns a body with a binary schema.
+fn binary_body(content_type: &str) -> T {
+    body(content_type, quote::quote!(|| ::catalyzer::internals::crates::serde_json::json!({
+        "type": "string", "contentEncoding": "binary",
+    })))
+}
+
+/// Describes the request body the argument extracts, if any.
+fn request_body_of(ty: &syn::Type) -> Option<T> {
+    if let Some(inner) = generic_arg(ty, "Json") {
+        return Some(body("application/json", schema_of(inner)));
+    }
+    if let Some(inner) = generic_arg(ty, "Form") {
+        return Some(body("application/x-www-form-urlencoded", schema_of(inner)));
+    }
+    match last_ident(ty)?.to_string().as_str() {
+        "String" => Some(body("text/plain", schema_of(ty))),
+        "Bytes" => Some(binary_body("application/octet-stream")),
+        _ => None,
+    }
+}
+
+/// Describes the response body the return type produces, if known.
+fn response_body_of(ty: &syn::Type) -> Option<T> {
+    if let syn::Type::Reference(reference) = ty {
+        return match last_ident(&reference.elem)? == "str" {
+            true => Some(body("text/plain", schema_of(&reference.elem))),
+            false => None,
+        };
+    }
+    if let Some(ok) = generic_arg(ty, "Result") {
+        return response_body_of(ok);
+    }
+    if let Some(inner) = generic_arg(ty, "Json") {
+        return Some(body("application/json", schema_of(inner)));
+    }
+    let string = || schema_of(&syn::parse_quote!(::std::string::String));
+    match last_ident(ty)?.to_string().as_str() {
+        "String" => Some(body("text/plain", string())),
+        "Html" => Some(body("text/html", string())),
+        "Css" => Some(body("text/css", string())),
+        "Js" => Some(body("application/javascript", string())),
+        "Json" => Some(body("application/json", string())),
+        "Bytes" => Some(binary_body("application/octet-stream")),
+        _ => None,
+    }
+}
+
+/// Returns the last identifier of a type path (e.g. `Json` for `axum::Json<T>`).
+fn last_ident(ty: &syn::Type) -> Option<&syn::Ident> {
+    match ty {
+        syn::Type::Path(p) => p.path.segments.last().map(|s| &s.ident),
+        _ => None,
+    }
+}
+
+/// Returns the first generic argument of a type path ending with `name` (e.g. `T` for `Json<T>`).
+fn generic_arg<'a>(ty: &'a syn::Type, name: &str) -> Option<&'a syn::Type> {
+    let syn::Type::Path(p) = ty else { return None; };
+    let last = p.path.segments.last()?;
+    if last.ident != name { return None; }
+    let syn::PathArguments::AngleBracketed(args) = &last.arguments else { return None; };
+    match args.args.first()? {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    }
+}
+
+/// Returns the state type the handler extracts with `State<T>`, or `()` if it has none.
+fn state_type(inputs: &Inputs) -> syn::Type {
+    for arg in inputs {
+        let syn::FnArg::Typed(typed) = arg else { continue; };
+        let syn::Type::Path(ty) = &*typed.ty else { continue; };
+        let Some(last) = ty.path.segments.last() else { continue; };
+        if last.ident != "State" { continue; }
+        if let syn::PathArguments::AngleBracketed(args) = &last.arguments {
+            if let Some(syn::GenericArgument::Type(state)) = args.args.first() {
+                return state.clone();
+            }
+        }
+    }
+    syn::parse_quote!(())
+}
+
+/// Returns the name of an argument bound to a plain identifier.
+fn arg_ident(arg: &syn::FnArg) -> Option<&syn::Ident> {
+    match arg {
+        syn::FnArg::Typed(syn::PatType { pat, .. }) => match &**pat {
+            syn::Pat::Ident(p) => Some(&p.ident),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether the argument is a `Path` extractor (Catalyzer's, or the router's).
+fn is_path_extractor(arg: &syn::FnArg) -> bool {
+    let syn::FnArg::Typed(typed) = arg else { return false; };
+    match &*typed.ty {
+        syn::Type::Path(p) => p.path.segments.last().is_some_and(|s| s.ident == "Path"),
+        _ => false,
+    }
+}
+
+/// Whether the type can only be a path parameter (it is not an extractor).
+fn is_scalar(ty: &syn::Type) -> bool {
+    const SCALARS: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize",
+        "i8", "i16", "i32", "i64", "i128", "isize",
+        "f32", "f64", "bool", "char",
+    ];
+    match ty {
+        syn::Type::Path(p) if p.qself.is_none() => p.path.get_ident()
+            .is_some_and(|i| SCALARS.iter().any(|s| i == s)),
+        _ => false,
+    }
+}
+
+/// Arguments of the `#[route]` attribute: `"/path", methods = [GET, POST]`.
+pub(crate) struct RouteArgs {
+    path: syn::LitStr,
+    methods: Vec<syn::LitStr>,
+}
+
+impl syn::parse::Parse for RouteArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        input.parse::<syn::Token![,]>()?;
+        let key = input.parse::<syn::Ident>()?;
+        if key != "methods" {
+            return Err(syn::Error::new(key.span(), "expected `methods = [...]`"));
+        }
+        input.parse::<syn::Token![=]>()?;
+        let list;
+        let brackets = syn::bracketed!(list in input);
+        let idents = list.parse_terminated(syn::Ident::parse, syn::Token![,])?;
+        input.parse::<Option<syn::Token![,]>>()?;
+        if idents.is_empty() {
+            return Err(syn::Error::new(brackets.span.join(), "expected at least one method"));
+        }
+        let mut methods = Vec::<syn::LitStr>::new();
+        for ident in idents {
+            let name = ident.to_string();
+            if !STANDARD_METHODS.contains(&name.as_str()) {
+                return Err(syn::Error::new(ident.span(), format!(
+                    "unknown method `{name}`, expected one of {}", STANDARD_METHODS.join(", ")
+                )));
+            }
+            if methods.iter().any(|m| m.value() == name) {
+                return Err(syn::Error::new(ident.span(), format!("duplicate method `{name}`")));
+            }
+            methods.push(syn::LitStr::new(&name, ident.span()));
+        }
+        Ok(Self { path, methods })
+    }
+}
+
+const STANDARD_METHODS: &[&str] = &[
+    "GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS", "TRACE", "CONNECT",
+];
+
+pub(crate) fn route(cfg: T, input: T) -> T {
+    match syn::parse2::<RouteArgs>(cfg) {
+        Err(e) => e.to_compile_error(),
+        Ok(args) => handler(Some(args.path), &args.methods, input),
+    }
+}
+
+pub(crate) fn fallback(cfg: T, input: T) -> T {
+    if !cfg.is_empty() {
+        return syn::Error::new(cfg.span(), "`#[fallback]` takes no arguments").to_compile_error();
+    }
+    handler(None, &[], input)
+}
+
+/// Arguments of the `#[method]` attribute: `"PROPFIND", "/path"`.
+pub(crate) struct MethodArgs {
+    method: syn::LitStr,
+    path: syn::LitStr,
+}
+
+impl syn::parse::Parse for MethodArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let method = input.parse::<syn::LitStr>()?;
+        input.parse::<syn::Token![,]>()?;
+        let path = input.parse()?;
+        input.parse::<Option<syn::Token![,]>>()?;
+        let name = method.value();
+        // Methods are tokens, as defined by RFC 9110.
+        let valid = !name.is_empty() && name.bytes().all(|b| {
+            b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+        });
+        if !valid {
+            return Err(syn::Error::new(method.span(), format!("invalid method `{name}`")));
+        }
+        Ok(Self { method, path })
+    }
+}
+
+pub(crate) fn method(cfg: T, input: T) -> T {
+    match syn::parse2::<MethodArgs>(cfg) {
+        Err(e) => e.to_compile_error(),
+        Ok(args) => handler(Some(args.path), &[args.method], input),
+    }
+}
+
+/// Expands a route handler, generating its metadata.
+/// 
+/// Handlers without a path are fallbacks.
+fn handler(path: Option<syn::LitStr>, methods: &[syn::LitStr], input: T) -> T {
+    let mut func = match syn::parse2::<syn::ItemFn>(input) {
+        Err(e) => return e.to_compile_error(),
+        Ok(f) => f,
+    };
+    if func.sig.asyncness.is_none() {
+        func.sig.asyncness = Some(syn::Token![async](func.span()));
+    };
+    let mut ret = func.sig.output.clone();
+    if let syn::ReturnType::Default = func.sig.output {
+        ret = syn::parse_quote! { -> impl ::catalyzer::res::IntoRawResponse }
+    }
+    let fallback = path.is_none();
+    let route = match path.as_ref().map(path::RoutePath::parse).transpose() {
+        Err(e) => return e.to_compile_error(),
+        Ok(r) => r,
+    };
+    let api = api_metadata(&func, route.as_ref());
+    let path = match (path, route) {
+        (Some(path), Some(route)) => {
+            if let Err(e) = bind_params(&route, &path, &mut func.sig.inputs) {
+                return e.to_compile_error();
+            }
+            syn::LitStr::new(&route.route, path.span())
+        },
+        _ => syn::LitStr::new("", func.sig.ident.span()),
+    };
+
+    let asyncness = &func.sig.asyncness;
+    let ident = &func.sig.ident;
+    let block = &func.block;
+    let vis = &func.vis;
+    let attrs = &func.attrs;
+    let generics = &func.sig.generics;
+    let where_clause = &func.sig.generics.where_clause;
+    let inputs = &func.sig.inputs;
+    let output = &ret;
+
+    let metadata_ident = format!("{ident}_metadata");
+    let metadata_ident = syn::Ident::new(&metadata_ident, ident.span());
+    let metadata = quote::quote! {
+        #[doc = #Watermark]
+        #[doc(hidden)]
+        #[repr(transparent)]
+        #[allow(non_camel_case_types)]
+        #vis struct #metadata_ident;
+        impl ::catalyzer::internals::HandlerMetadata for #metadata_ident {
+            const NAME: &'static str = ::core::stringify!(#ident);
+            const PATH: &'static str = #path;
+            const METHODS: &'static [&'static str] = &[#(#methods),*];
+            const FALLBACK: bool = #fallback;
+            const LOCATION: ::core::option::Option<&'static str> = ::core::option::Option::Some(
+                ::core::concat!(::core::file!(), ":", ::core::line!())
+            );
+            #api
+        }
+    };
+
+    let register = match generics.params.is_empty() {
+        true => {
+            let state = state_type(inputs);
+            quote::quote!(::catalyzer::__register_handler!(#ident, #metadata_ident, #state);)
+        },
+        false => quote::quote!(),
+    };
+
+    quote::quote!(
+        #metadata
+        #register
+        #(#attrs)*
+        #vis #asyncness fn #ident #generics (#inputs) #output #where_clause #block
+    )
+}
+
 macro_rules! routes {
     ($($name:ident($method:ident))+)=>($(
         pub(crate) fn $name(path: T, input: T) -> T {
-            let mut func = match syn::parse2::<syn::ItemFn>(input) {
-                Err(e) => return e.to_compile_error(),
-                Ok(f) => f,
-            };
-            if func.sig.asyncness.is_none() {
-                func.sig.asyncness = Some(syn::Token![async](func.span()));
-            };
-            let mut ret = func.sig.output.clone();
-            match func.sig.output {
-                syn::ReturnType::Default => {
-                    ret = syn::parse_quote! { -> impl ::catalyzer::res::IntoRawResponse }
+            match syn::parse2::<syn::LitStr>(path) {
+                Err(e) => e.to_compile_error(),
+                Ok(path) => {
+                    let method = syn::LitStr::new(stringify!($method), path.span());
+                    handler(Some(path), &[method], input)
                 },
-                _ => {}
-            };
-            let path = match syn::parse2::<syn::LitStr>(path) {
-                Err(e) => return e.to_compile_error(),
-                Ok(p) => p,
-            };
-        
-            let asyncness = &func.sig.asyncness;
-            let ident = &func.sig.ident;
-            let block = &func.block;
-            let vis = &func.vis;
-            let attrs = &func.attrs;
-            let generics = &func.sig.generics;
-            let where_clause = &func.sig.generics.where_clause;
-            let inputs = &func.sig.inputs;
-            let output = &ret;
-        
-            let metadata_ident = format!("{ident}_metadata");
-            let metadata_ident = syn::Ident::new(&metadata_ident, ident.span());
-            let metadata = quote::quote! {
-                #[doc = #Watermark]
-                #[doc(hidden)]
-                #[repr(transparent)]
-                #[allow(non_camel_case_types)]
-                struct #metadata_ident;
-                impl ::catalyzer::internals::HandlerMetadata for #metadata_ident {
-                    const PATH: &'static str = #path;
-                    const METHOD: ::catalyzer::internals::Method = ::catalyzer::internals::Method::$method;
-                }
-            };
-        
-            quote::quote!(
-                #metadata
-                #(#attrs)*
-                #vis #asyncness fn #ident #generics (#inputs) #output #where_clause #block
-            )
+            }
         }
     )+)
 }
@@ -74,4 +459,5 @@ routes! {
     head(HEAD)
     options(OPTIONS)
     trace(TRACE)
+    connect(CONNECT)
 }
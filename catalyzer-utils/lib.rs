@@ -55,10 +55,7 @@ impl<T> OptionExt<T> for Option<T> {
     fn map_auto<T2>(self) -> Option<T2> where
         T2: From<T>
     {
-        match self {
-            None => None,
-            Some(t) => Some(T2::from(t)),
-        }
+        self.map(T2::from)
     }
 
     #[inline]
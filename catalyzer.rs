@@ -8,7 +8,7 @@
 //! 
 //! # Example
 //! 
-//! ```rust
+//! ```rust,no_run
 //! use catalyzer::*;
 //! 
 //! #[main]